@@ -47,7 +47,7 @@ fn benchmark_scout_2(c: &mut Criterion) {
     ];
     c.bench_function("scout_2_scenario", |b| {
         b.iter(|| {
-            brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 1, false);
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 1);
         })
     });
 }
@@ -82,7 +82,7 @@ fn benchmark_scout(c: &mut Criterion) {
 
     c.bench_function("scout_scenario", |b| {
         b.iter(|| {
-            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 1, 0, false);
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 1, 0);
         })
     });
 }
@@ -120,7 +120,7 @@ fn benchmark_jester(c: &mut Criterion) {
 
     c.bench_function("jester_scenario", |b| {
         b.iter(|| {
-            brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 2, 0, false);
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 2, 0);
         })
     });
 }
@@ -167,7 +167,7 @@ fn benchmark_twin_and_medium(c: &mut Criterion) {
 
     c.bench_function("twin_and_medium_scenario", |b| {
         b.iter(|| {
-            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 2, 0, false);
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 2, 0);
         })
     });
 }
@@ -213,17 +213,143 @@ fn benchmark_empress_empress_empress(c: &mut Criterion) {
 
     c.bench_function("empress_empress_empress_scenario", |b| {
         b.iter(|| {
-            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0, false);
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
         })
     });
 }
 
+fn benchmark_wretch_many_minions(c: &mut Criterion) {
+    use Role::*;
+    let deck = vec![
+        Confessor,
+        Lover,
+        Hunter,
+        Knight,
+        Scout,
+        Enlightened,
+        Wretch,
+        Minion,
+        Minion,
+        Poisoner,
+        Witch,
+        TwinMinion,
+        Puppeteer,
+    ];
+    let visible = vec![
+        Some(Wretch),
+        Some(Confessor),
+        Some(Lover),
+        None,
+        Some(Hunter),
+        None,
+        Some(Scout),
+    ];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![
+        RoleStatement::NoStatement,
+        ConfessorStatement::IAmGood.into(),
+        LoverStatement { evil_count: 1 }.into(),
+        RoleStatement::NoStatement,
+        HunterStatement { distance: 1 }.into(),
+        RoleStatement::NoStatement,
+        ScoutStatement {
+            role: Some(Witch),
+            distance: 1,
+        }
+        .into(),
+    ];
+
+    c.bench_function("wretch_many_minions_scenario", |b| {
+        b.iter(|| {
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 2, 0);
+        })
+    });
+}
+
+/// Same board as `test_wretch`. Seats showing a role can only hold that role
+/// or an evil disguised as it, which rules out most seatings early. Cutting
+/// those off while seating took this from about 3.9 ms to 12 µs.
+fn benchmark_wretch(c: &mut Criterion) {
+    use Role::*;
+
+    let deck = vec![
+        Hunter, Empress, Lover, Gemcrafter, Confessor, Wretch, Minion,
+    ];
+
+    let visible = vec![
+        Some(Empress),
+        Some(Lover),
+        Some(Confessor),
+        None,
+        Some(Lover),
+        None,
+        Some(Hunter),
+    ];
+
+    let confirmed = vec![None; visible.len()];
+
+    let observed = vec![
+        EmpressStatement {
+            target_indexes: to_bitvec(vec![5, 2, 3]),
+        }
+        .into(),
+        LoverStatement { evil_count: 0 }.into(),
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        LoverStatement { evil_count: 0 }.into(),
+        RoleStatement::NoStatement,
+        HunterStatement { distance: 2 }.into(),
+    ];
+
+    c.bench_function("wretch_scenario", |b| {
+        b.iter(|| {
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 0);
+        })
+    });
+}
+
+/// No seat revealed and nothing said, so every seating and disguise of the
+/// deck gets tried. The baseline when this was added was about 1.6 s per
+/// solve, hence the smaller sample size.
+fn benchmark_all_unrevealed(c: &mut Criterion) {
+    use Role::*;
+
+    let deck = vec![
+        Lover,
+        Confessor,
+        Enlightened,
+        Scout,
+        Knight,
+        Hunter,
+        Medium,
+        Bombardier,
+        Witch,
+        Baa,
+    ];
+
+    let visible = vec![None; 8];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let mut group = c.benchmark_group("all_unrevealed");
+    group.sample_size(10);
+    group.bench_function("all_unrevealed_scenario", |b| {
+        b.iter(|| {
+            brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 1);
+        })
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_scout_2,
     benchmark_scout,
     benchmark_jester,
     benchmark_twin_and_medium,
-    benchmark_empress_empress_empress
+    benchmark_empress_empress_empress,
+    benchmark_wretch_many_minions,
+    benchmark_all_unrevealed,
+    benchmark_wretch
 );
 criterion_main!(benches);
@@ -0,0 +1,68 @@
+use crate::roles::*;
+
+/// How many roles of each group a deck holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeckInfo {
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+    has_counsellor: bool,
+}
+
+impl From<&[Role]> for DeckInfo {
+    fn from(deck: &[Role]) -> Self {
+        let count = |group: Group| deck.iter().filter(|r| r.group() == group).count();
+        DeckInfo {
+            villagers: count(Group::Villager),
+            outcasts: count(Group::Outcast),
+            minions: count(Group::Minion),
+            demons: count(Group::Demon),
+            has_counsellor: deck.contains(&Role::Counsellor),
+        }
+    }
+}
+
+impl DeckInfo {
+    pub fn villager_count(&self) -> usize {
+        self.villagers
+    }
+
+    pub fn outcast_count(&self) -> usize {
+        self.outcasts
+    }
+
+    pub fn minion_count(&self) -> usize {
+        self.minions
+    }
+
+    pub fn demon_count(&self) -> usize {
+        self.demons
+    }
+
+    /// Every `(villagers, outcasts, minions, demons)` split of `seats` this deck
+    /// can fill. A Counsellor turns one of the villagers into an extra outcast,
+    /// so splits that can only be filled by playing the Counsellor need an
+    /// outcast left in the deck for it.
+    pub fn valid_distributions(&self, seats: usize) -> Vec<(usize, usize, usize, usize)> {
+        let mut distributions = Vec::new();
+        for demons in 0..=self.demons.min(seats) {
+            for minions in 0..=self.minions.min(seats - demons) {
+                for outcasts in 0..=self.outcasts.min(seats - demons - minions) {
+                    let villagers = seats - demons - minions - outcasts;
+                    if villagers > self.villagers {
+                        continue;
+                    }
+
+                    let needs_counsellor = self.has_counsellor && minions == self.minions;
+                    if needs_counsellor && (villagers == 0 || outcasts == self.outcasts) {
+                        continue;
+                    }
+
+                    distributions.push((villagers, outcasts, minions, demons));
+                }
+            }
+        }
+        distributions
+    }
+}
@@ -1,7 +1,32 @@
+pub mod deck;
+pub mod logging;
+pub mod puzzle;
+pub mod render;
+pub mod repl;
 pub mod roles;
 pub mod runner;
+pub mod share;
+pub mod solution;
 pub mod solver;
 
+pub use deck::DeckInfo;
+pub use logging::enable_trace_logging;
+pub use puzzle::Puzzle;
+pub use render::{render_board, render_board_numbered, solutions_to_csv, Coloring};
+pub use repl::{run_repl, run_repl_with};
 pub use roles::{Role, RoleStatement};
-pub use runner::{run_args, run_clipboard_loop, run_from_clipboard};
-pub use solver::{brute_force_solve, validate_candidate};
+pub use runner::{
+    parse_cli_puzzle, parse_clipboard_puzzle, poll_clipboard, run_args, run_args_to,
+    run_clipboard_loop, run_clipboard_loop_with, run_from_clipboard, ClipboardSource, RunOutcome,
+};
+pub use share::{decode_puzzle, encode_puzzle};
+pub use solution::{format_solution_detailed, Solution};
+pub use solver::{
+    brute_force_solve, brute_force_solve_constrained, brute_force_solve_detailed,
+    brute_force_solve_with_progress, can_show_as, consistent_statements_for_seat,
+    consistent_statements_for_seat_on, definite_alignments, demon_seat_distribution,
+    diagnose_infeasible, distinct_up_to_rotation, forced_liars, group_by_demon,
+    minimal_statement_set, solve_batch, solve_iter, solve_iter_shuffled, solve_with_stats,
+    summarize_positions, try_solve, try_solve_detailed, unused_deck_roles, validate_candidate,
+    validate_candidate_on, Constraints, PositionSummary, SolveError, SolveStats, SolverContext,
+};
@@ -0,0 +1,26 @@
+use log::{LevelFilter, Metadata, Record};
+
+/// Writes every record to stderr, for when the host doesn't bring its own logger
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        eprintln!("{}", record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Sends the solver's trace logging, like why each rejected board didn't fit,
+/// to stderr. Does nothing if a logger is already installed.
+pub fn enable_trace_logging() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Trace);
+    }
+}
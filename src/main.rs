@@ -1,7 +1,15 @@
-use demon_deduce::{run_args, run_clipboard_loop, run_from_clipboard};
+use demon_deduce::{
+    enable_trace_logging, run_args, run_clipboard_loop, run_from_clipboard, run_repl,
+};
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+
+    // Prints why every rejected board didn't fit, works with all the modes
+    if let Some(verbose_pos) = args.iter().position(|x| x == "--verbose") {
+        args.remove(verbose_pos);
+        enable_trace_logging();
+    }
 
     if args.contains(&"-c".to_string()) {
         run_from_clipboard();
@@ -13,5 +21,10 @@ fn main() {
         return;
     }
 
+    if args.contains(&"-r".to_string()) {
+        run_repl();
+        return;
+    }
+
     run_args(args);
 }
@@ -0,0 +1,96 @@
+use crate::roles::*;
+use crate::solver::{can_show_as, SolveError};
+
+/// Everything the solver needs to know about one game
+#[derive(Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    pub deck: Vec<Role>,
+    /// Role shown face-up on each seat, `None` if unrevealed
+    pub visible: Vec<Option<Role>>,
+    /// True role of each seat, `None` if unknown
+    pub confirmed: Vec<Option<Role>>,
+    /// Statement made by each seat
+    pub observed: Vec<RoleStatement>,
+    pub villagers: usize,
+    pub outcasts: usize,
+    pub minions: usize,
+    pub demons: usize,
+}
+
+impl Puzzle {
+    /// One message per visible or confirmed role that isn't in the deck. Every
+    /// disguise is drawn from the deck too, so such a seat can never be solved.
+    pub fn roles_not_in_deck(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (i, (visible, confirmed)) in self.visible.iter().zip(&self.confirmed).enumerate() {
+            if let Some(role) = visible.filter(|role| !self.deck.contains(role)) {
+                errors.push(format!(
+                    "Seat {} shows {:?}, which is not in deck",
+                    i + 1,
+                    role
+                ));
+            }
+            if let Some(role) = confirmed.filter(|role| !self.deck.contains(role)) {
+                errors.push(format!(
+                    "Seat {} is confirmed as {:?}, which is not in deck",
+                    i + 1,
+                    role
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Cheap checks that the puzzle is well-formed, without solving it. Every
+    /// problem found is reported, not just the first.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let seats = self.visible.len();
+        if self.confirmed.len() != seats || self.observed.len() != seats {
+            errors.push(
+                SolveError::LengthMismatch {
+                    visible: seats,
+                    confirmed: self.confirmed.len(),
+                    observed: self.observed.len(),
+                }
+                .to_string(),
+            );
+        }
+
+        let counts = self.villagers + self.outcasts + self.minions + self.demons;
+        if counts != seats {
+            errors.push(SolveError::CountSumMismatch { counts, seats }.to_string());
+        }
+
+        for (seat, statement) in self.observed.iter().enumerate() {
+            if !targets_in_range(statement, seats) {
+                errors.push(SolveError::IndexOutOfRange { seat, seats }.to_string());
+            }
+            if names_speaker(statement, seat) {
+                errors.push(SolveError::NamesSpeaker { seat }.to_string());
+            }
+        }
+
+        for (seat, (confirmed, visible)) in self.confirmed.iter().zip(&self.visible).enumerate() {
+            if let (Some(confirmed), Some(visible)) = (*confirmed, *visible) {
+                if !can_show_as(&self.deck, confirmed, visible) {
+                    errors.push(
+                        SolveError::UnshowableConfirmed {
+                            seat,
+                            confirmed,
+                            visible,
+                        }
+                        .to_string(),
+                    );
+                }
+            }
+        }
+
+        errors.extend(self.roles_not_in_deck());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::roles::*;
+use colored::*;
+use std::fmt::Write;
+
+/// How to color role names in rendered output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coloring {
+    /// Plain text, for logs or anything that isn't a terminal
+    None,
+    /// Green for good and red for evil
+    Alignment,
+    /// A different color for each group
+    Group,
+}
+
+impl Coloring {
+    /// The role's in-game name, colored by this strategy
+    pub fn paint(self, role: Role) -> String {
+        let name = role.display_name();
+        match self {
+            Coloring::None => name.to_string(),
+            Coloring::Alignment => match role.alignment() {
+                Alignment::Good => name.green().to_string(),
+                Alignment::Evil => name.red().to_string(),
+            },
+            Coloring::Group => match role.group() {
+                Group::Villager => name.green().to_string(),
+                Group::Outcast => name.yellow().to_string(),
+                Group::Minion => name.red().to_string(),
+                Group::Demon => name.bright_red().to_string(),
+            },
+        }
+    }
+}
+
+/// One `Player i: visible (confirmed) - statement` line per seat. When `board`
+/// isn't empty each line also ends with that seat's true role.
+pub fn render_board(
+    board: &[Role],
+    visible: &[Option<Role>],
+    confirmed: &[Option<Role>],
+    observed: &[RoleStatement],
+    coloring: Coloring,
+) -> String {
+    render_board_numbered(0, board, visible, confirmed, observed, coloring)
+}
+
+/// Same as `render_board`, but the first seat is called `first_seat`, e.g. 1
+/// to number them like in the game. Seats named in statements move along.
+pub fn render_board_numbered(
+    first_seat: usize,
+    board: &[Role],
+    visible: &[Option<Role>],
+    confirmed: &[Option<Role>],
+    observed: &[RoleStatement],
+    coloring: Coloring,
+) -> String {
+    let mut rendered = String::new();
+    for i in 0..visible.len() {
+        let vis = match visible[i] {
+            Some(role) => {
+                let confirmed_part = match confirmed[i] {
+                    Some(c_role) if c_role != role => format!(" ({})", c_role.display_name()),
+                    _ => String::new(),
+                };
+                format!("{}{}", role.display_name(), confirmed_part)
+            }
+            None => "Unrevealed".to_string(),
+        };
+
+        // A statement naming the last of 16 seats can't be moved up, it's shown
+        // with its seats as they are
+        let statement = match observed[i].clone().offset_seats(first_seat) {
+            Some(statement) => statement.to_string(),
+            None => format!("{} (seats from 0)", observed[i]),
+        };
+        write!(
+            rendered,
+            "Player {}: {} - {}",
+            first_seat + i,
+            vis,
+            statement
+        )
+        .unwrap();
+        if let Some(&role) = board.get(i) {
+            write!(rendered, " => {}", coloring.paint(role)).unwrap();
+        }
+        rendered.push('\n');
+    }
+    rendered
+}
+
+/// The solutions as CSV, a `seat_1,seat_2,...` header for the `seats` seats and
+/// then one row of role names per solution. Without any solutions it's only
+/// the header, so a reader still sees the columns.
+pub fn solutions_to_csv(seats: usize, solutions: &[Vec<Role>]) -> String {
+    let mut csv = String::new();
+    let header: Vec<String> = (1..=seats).map(|seat| format!("seat_{}", seat)).collect();
+    writeln!(csv, "{}", header.join(",")).unwrap();
+    for solution in solutions {
+        let row: Vec<&str> = solution.iter().map(|role| role.display_name()).collect();
+        writeln!(csv, "{}", row.join(",")).unwrap();
+    }
+    csv
+}
@@ -0,0 +1,185 @@
+use crate::puzzle::Puzzle;
+use crate::render::{render_board_numbered, Coloring};
+use crate::roles::*;
+use crate::runner::{parse_cli_puzzle, write_possible_roles};
+use crate::solver::{can_show_as, SolverContext};
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+const HELP: &str = "Commands:
+  load <deck> <villagers> <outcasts> <minions> <demons> [cards...]  same as the command line
+  reveal <seat> <role>      the seat shows this role
+  confirm <seat> <role>     the seat is known to really be this role
+  claim <seat> <statement>  the seat's statement, in the command line format with seats from 1
+  show                      print the board
+  help                      print this
+  quit";
+
+/// Interactive mode reading commands from stdin, see `run_repl_with`
+pub fn run_repl() {
+    let stdin = io::stdin();
+    run_repl_with(stdin.lock(), &mut io::stdout()).expect("Failed to write output");
+}
+
+/// Keeps a puzzle around and re-solves it after every command that changes
+/// it, printing the roles each seat can still be. Seats are 1-indexed like in
+/// the game. Confirming a seat that had no confirmed role only narrows the
+/// board, so that filters the previous solutions instead of solving again.
+pub fn run_repl_with(input: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    // The deck and counts only change on `load`, so the context outlives the other commands
+    let mut loaded: Option<(Puzzle, SolverContext)> = None;
+    let mut solutions: Vec<Vec<Role>> = Vec::new();
+
+    writeln!(out, "{}", HELP)?;
+    for line in input.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some(&command) = words.first() else {
+            continue;
+        };
+
+        match command {
+            "quit" | "exit" => break,
+            "help" => writeln!(out, "{}", HELP)?,
+            "load" => {
+                let args: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+                match parse_cli_puzzle(&args) {
+                    Ok(puzzle) => {
+                        let context = SolverContext::new(
+                            &puzzle.deck,
+                            puzzle.villagers,
+                            puzzle.outcasts,
+                            puzzle.minions,
+                            puzzle.demons,
+                        );
+                        solutions = solve(out, &context, &puzzle)?;
+                        loaded = Some((puzzle, context));
+                    }
+                    Err(e) => writeln!(out, "{}", e)?,
+                }
+            }
+            "show" | "reveal" | "confirm" | "claim" => {
+                let Some((puzzle, context)) = loaded.as_mut() else {
+                    writeln!(out, "Load a puzzle first")?;
+                    continue;
+                };
+                match command {
+                    "show" => write!(
+                        out,
+                        "{}",
+                        render_board_numbered(
+                            1,
+                            &[],
+                            &puzzle.visible,
+                            &puzzle.confirmed,
+                            &puzzle.observed,
+                            Coloring::None
+                        )
+                    )?,
+                    "confirm" => {
+                        let before = puzzle.confirmed.clone();
+                        match apply(puzzle, command, &words) {
+                            Ok((seat, role)) => {
+                                // A first confirmation only narrows the board, changing one
+                                // can bring back boards that were filtered out. One the seat
+                                // can't show goes to the solver to be reported.
+                                let shows = puzzle.visible[seat]
+                                    .is_none_or(|shown| can_show_as(&puzzle.deck, role, shown));
+                                if before[seat].is_none() && shows {
+                                    solutions.retain(|solution| solution[seat] == role);
+                                    write_solutions(out, &solutions)?;
+                                } else {
+                                    solutions = solve(out, context, puzzle)?;
+                                }
+                            }
+                            Err(e) => writeln!(out, "{}", e)?,
+                        }
+                    }
+                    _ => match apply(puzzle, command, &words) {
+                        Ok(_) => solutions = solve(out, context, puzzle)?,
+                        Err(e) => writeln!(out, "{}", e)?,
+                    },
+                }
+            }
+            _ => writeln!(out, "Unknown command '{}', try 'help'", command)?,
+        }
+    }
+    Ok(())
+}
+
+/// Applies a `reveal`, `confirm` or `claim` to the puzzle, returning the
+/// 0-indexed seat and the role it's about
+fn apply(puzzle: &mut Puzzle, command: &str, words: &[&str]) -> Result<(usize, Role), String> {
+    if words.len() < 3 {
+        return Err(format!(
+            "Usage: {} <seat> <{}>",
+            command,
+            match command {
+                "claim" => "statement",
+                _ => "role",
+            }
+        ));
+    }
+    let seat = match words[1].parse::<usize>() {
+        Ok(seat) if (1..=puzzle.visible.len()).contains(&seat) => seat - 1,
+        _ => {
+            return Err(format!(
+                "Invalid seat '{}' (must be 1-{})",
+                words[1],
+                puzzle.visible.len()
+            ))
+        }
+    };
+
+    if command == "claim" {
+        let role = puzzle.visible[seat]
+            .ok_or_else(|| format!("Seat {} is unrevealed, reveal it first", seat + 1))?;
+        let statement = role
+            .parse_statement_one_based(&words[2..].join(" "))
+            .map_err(|e| format!("Invalid statement for {:?}: {}", role, e))?;
+        puzzle.observed[seat] = statement;
+        return Ok((seat, role));
+    }
+
+    let role = Role::from_str(&words[2].to_lowercase())
+        .map_err(|e| format!("Failed to parse role '{}': {}", words[2], e))?;
+    if !puzzle.deck.contains(&role) {
+        return Err(format!("{:?} is not in deck", role));
+    }
+    if command == "reveal" {
+        // The old statement belonged to whatever the seat showed before
+        if puzzle.visible[seat] != Some(role) {
+            puzzle.observed[seat] = RoleStatement::NoStatement;
+        }
+        puzzle.visible[seat] = Some(role);
+    } else {
+        puzzle.confirmed[seat] = Some(role);
+    }
+    Ok((seat, role))
+}
+
+/// Solves the puzzle and prints the solutions, or why it can't be solved
+fn solve(
+    out: &mut impl Write,
+    context: &SolverContext,
+    puzzle: &Puzzle,
+) -> io::Result<Vec<Vec<Role>>> {
+    match context.try_solve_with(&puzzle.visible, &puzzle.confirmed, &puzzle.observed) {
+        Ok(solutions) => {
+            write_solutions(out, &solutions)?;
+            Ok(solutions)
+        }
+        Err(e) => {
+            writeln!(out, "Invalid puzzle: {}", e)?;
+            Ok(Vec::new())
+        }
+    }
+}
+
+fn write_solutions(out: &mut impl Write, solutions: &[Vec<Role>]) -> io::Result<()> {
+    if solutions.is_empty() {
+        return writeln!(out, "No solutions found.");
+    }
+    writeln!(out, "Found {} solution(s)", solutions.len())?;
+    write_possible_roles(out, solutions)
+}
@@ -1,9 +1,10 @@
 use bitvec::prelude::*;
+use itertools::Itertools;
 use std::fmt;
 use std::str::FromStr;
 use strum_macros::{Display, EnumIter, EnumString};
 
-type TargetIndexes = BitArray<[u8; 2], Lsb0>;
+pub(crate) type TargetIndexes = BitArray<[u8; 2], Lsb0>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, Display)]
 #[strum(serialize_all = "lowercase")]
@@ -20,23 +21,37 @@ pub enum Role {
     Druid,
     Empress,
     Enlightened,
+    /// Unlike its Blood on the Clocktower namesake there's no red herring, a
+    /// good seat never registers as evil, so a truthful answer only depends on
+    /// the named seats' real alignment.
     #[strum(
         serialize = "fortuneteller",
         serialize = "fortune teller",
         serialize = "fortune"
     )]
     FortuneTeller,
+    /// Also parsed as Archivist, the same card under an older name. Under
+    /// either name it learns that one seat is good, so it shares the
+    /// Gemcrafter statement.
     #[strum(serialize = "gemcrafter", serialize = "archivist")]
     Gemcrafter,
     Hunter,
     Jester,
     Judge,
+    /// Its ability only keeps itself alive, it never learns or says anything
+    /// about the board. There is no Knight statement, so it only ever
+    /// produces `NoStatement`.
     Knight,
     Knitter,
     Lover,
+    /// Also parsed as Lookout, its name in older builds of the game. Both names
+    /// are the same card that learns a good seat's real role, so one variant
+    /// and one statement cover them.
     #[strum(serialize = "medium", serialize = "lookout")]
     Medium,
     Oracle,
+    /// Also parsed as Gossip. It has no statement under either name, so it
+    /// only ever produces `NoStatement`.
     #[strum(serialize = "poet", serialize = "gossip")]
     Poet,
     Scout,
@@ -44,6 +59,9 @@ pub enum Role {
     Slayer,
     Witness,
     // Outcast
+    /// Explodes when its card is revealed, which only costs the player health.
+    /// It doesn't corrupt, move or otherwise change what anyone says, so for
+    /// deduction it's a plain Outcast without a statement.
     Bombardier,
     #[strum(serialize = "doppelganger", serialize = "doppleganger")]
     DoppelGanger,
@@ -71,6 +89,9 @@ pub enum Role {
         serialize = "twin"
     )]
     TwinMinion,
+    /// The curse only hurts the player when they reveal the cursed card, the
+    /// cursed card still tells the truth. So for deduction the Witch is a plain
+    /// lying, disguised Minion and not a source of corruption.
     Witch,
     // Demon
     #[strum(serialize = "baa", serialize = "imp")]
@@ -100,12 +121,51 @@ pub enum Group {
     Demon,
 }
 
+impl FromStr for Group {
+    type Err = String;
+
+    /// Case-insensitive, singular or plural, e.g. "Outcasts"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        match name.strip_suffix('s').unwrap_or(&name) {
+            "villager" => Ok(Group::Villager),
+            "outcast" => Ok(Group::Outcast),
+            "minion" => Ok(Group::Minion),
+            "demon" => Ok(Group::Demon),
+            _ => Err(format!("Unknown group '{}'", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Alignment {
     Good,
     Evil,
 }
 
+impl Group {
+    /// The alignment roles in this group have, villagers and outcasts are good
+    pub const fn default_alignment(self) -> Alignment {
+        match self {
+            Group::Villager | Group::Outcast => Alignment::Good,
+            Group::Minion | Group::Demon => Alignment::Evil,
+        }
+    }
+}
+
+impl Alignment {
+    pub const fn opposite(self) -> Alignment {
+        match self {
+            Alignment::Good => Alignment::Evil,
+            Alignment::Evil => Alignment::Good,
+        }
+    }
+
+    pub const fn is_evil(self) -> bool {
+        matches!(self, Alignment::Evil)
+    }
+}
+
 impl Role {
     pub const fn group(self) -> Group {
         use Role::*;
@@ -144,20 +204,131 @@ impl Role {
             | TwinMinion | Witch => true,
         }
     }
-    pub fn parse_statement(&self, s: &str) -> Result<RoleStatement, String> {
-        fn parse_indexes(s: &str) -> Result<TargetIndexes, String> {
+    /// Seat distances (both directions) a truthful role clears corruptions at
+    pub const fn cure_offsets(self) -> &'static [usize] {
+        match self {
+            Role::Alchemist => &[1, 2],
+            _ => &[],
+        }
+    }
+    /// Whether the role corrupts seats when the game is set up. The Drunk
+    /// only corrupts itself, the others pick villagers.
+    pub const fn is_corruption_source(self) -> bool {
+        matches!(
+            self,
+            Role::Drunk | Role::Pooka | Role::Poisoner | Role::PlagueDoctor
+        )
+    }
+    /// Whether the role clears corruption around it, see `cure_offsets`
+    pub const fn is_uncorruption_source(self) -> bool {
+        !self.cure_offsets().is_empty()
+    }
+    /// Human readable name as shown in game, for output. `Debug` and the strum
+    /// name are the parse/identifier forms.
+    pub const fn display_name(self) -> &'static str {
+        use Role::*;
+        match self {
+            Alchemist => "Alchemist",
+            Architect => "Architect",
+            Baker => "Baker",
+            Bard => "Bard",
+            Bishop => "Bishop",
+            Confessor => "Confessor",
+            Dreamer => "Dreamer",
+            Druid => "Druid",
+            Empress => "Empress",
+            Enlightened => "Enlightened",
+            FortuneTeller => "Fortune Teller",
+            Gemcrafter => "Gemcrafter",
+            Hunter => "Hunter",
+            Jester => "Jester",
+            Judge => "Judge",
+            Knight => "Knight",
+            Knitter => "Knitter",
+            Lover => "Lover",
+            Medium => "Medium",
+            Oracle => "Oracle",
+            Poet => "Poet",
+            Scout => "Scout",
+            Slayer => "Slayer",
+            Witness => "Witness",
+            Bombardier => "Bombardier",
+            DoppelGanger => "Doppelganger",
+            Drunk => "Drunk",
+            PlagueDoctor => "Plague Doctor",
+            Wretch => "Wretch",
+            Counsellor => "Counsellor",
+            Minion => "Minion",
+            Poisoner => "Poisoner",
+            Puppet => "Puppet",
+            Puppeteer => "Puppeteer",
+            Shaman => "Shaman",
+            TwinMinion => "Twin Minion",
+            Witch => "Witch",
+            Baa => "Baa",
+            Lilis => "Lilis",
+            Pooka => "Pooka",
+        }
+    }
+    /// The statement format `parse_statement` expects for the role, with seats
+    /// numbered from 0. `None` for roles it can't parse statements for.
+    pub const fn statement_help(self) -> Option<&'static str> {
+        use Role::*;
+        Some(match self {
+            Alchemist => "alchemist: number of corruptions cured, e.g. '1'",
+            Architect => "architect: 'left', 'right' or 'equal'",
+            Bard => "bard: distance to the closest corrupted seat, or 'none'",
+            Bishop => "bishop: comma list of seat indices, e.g. '0,2,4'",
+            Confessor => "confessor: 'iamgood' or 'iamdizzy'",
+            Dreamer => "dreamer: 'target_index;role', or 'target_index;!role' if it can't be the role, e.g. '3;minion'",
+            Druid => "druid: 'target_indexes;role', e.g. '0,2,4;wretch'",
+            Empress => "empress: comma list of seat indices, e.g. '0,2,4'",
+            Enlightened => "enlightened: 'clockwise', 'counterclockwise' or 'equidistant'",
+            FortuneTeller => "fortuneteller: 'target_indexes;true|false', e.g. '1,3;true'",
+            Gemcrafter => "gemcrafter: index of the good seat, e.g. '2'",
+            Hunter => "hunter: distance to the closest evil seat, e.g. '2'",
+            Jester => "jester: 'target_indexes;evil_count', e.g. '0,2,4;1'",
+            Judge => "judge: 'target_index;truthy|lying', e.g. '3;lying'",
+            Knitter => "knitter: number of adjacent evil pairs, e.g. '1'",
+            Lover => "lover: number of evil neighbours, e.g. '1'",
+            Medium => "medium: 'target_index;role', e.g. '3;knight'",
+            Oracle => {
+                "oracle: 'target_indexes;role|group' with at least 2 seats, e.g. '1,3;outcast', 'group:minion' for any minion"
+            }
+            Baker => "baker: no statement, leave it empty",
+            Bombardier => "bombardier: no statement, leave it empty",
+            Knight => "knight: no statement, leave it empty",
+            Poet => "poet: no statement, leave it empty",
+            Scout => "scout: 'role;distance', e.g. 'minion;2', or 'none'",
+            Slayer => "slayer: 'target_index;good|evil', e.g. '3;evil'",
+            PlagueDoctor => "plaguedoctor: 'corrupt_index', or 'evil_index;corrupt_index'",
+            Wretch => "wretch: no statement, leave it empty",
+            DoppelGanger | Drunk | Baa | Lilis | Minion | Poisoner | Pooka | Puppet | Puppeteer
+            | Shaman | TwinMinion | Witch | Counsellor | Witness => return None,
+        })
+    }
+    pub fn parse_statement(&self, s: &str) -> Result<RoleStatement, StatementParseError> {
+        fn parse_indexes(s: &str) -> Result<TargetIndexes, StatementParseError> {
             let mut bits = TargetIndexes::default();
 
             for (i, idx_str) in s.split(',').enumerate() {
                 let idx_str = idx_str.trim();
                 let idx: usize = idx_str.parse().map_err(|_| {
-                    format!(
+                    StatementParseError::InvalidIndex(format!(
                         "Invalid index '{}' at position {} in '{}'",
                         idx_str,
                         i + 1,
                         s
-                    )
+                    ))
                 })?;
+                if idx >= bits.len() {
+                    return Err(StatementParseError::InvalidIndex(format!(
+                        "Index {} at position {} in '{}' is too large",
+                        idx,
+                        i + 1,
+                        s
+                    )));
+                }
                 bits.set(idx, true);
             }
 
@@ -167,7 +338,7 @@ impl Role {
         match self {
             Role::Alchemist => {
                 let corrupt_count = s.trim().parse().map_err(|_| {
-                    format!("Invalid corrupt count '{}' for Alchemist", s)
+                    StatementParseError::InvalidKeyword(format!("Invalid corrupt count '{}' for Alchemist", s))
                 })?;
                 Ok(AlchemistStatement { corrupt_count }.into())
             }
@@ -175,17 +346,17 @@ impl Role {
                 "right" => Ok(ArchitectStatement::Right.into()),
                 "left" => Ok(ArchitectStatement::Left.into()),
                 "equal" => Ok(ArchitectStatement::Equal.into()),
-                _ => Err(format!(
+                _ => Err(StatementParseError::InvalidKeyword(format!(
                     "Invalid Architect statement '{}' - expected 'left', 'right', or 'equal'",
                     s
-                )),
+                ))),
             }
             Role::Bard => {
                 let distance = if s.trim() == "none" {
                     None
                 } else {
                     Some(s.trim().parse().map_err(|_| {
-                        format!("Invalid distance '{}' for Bard - expected 'none' or a number", s)
+                        StatementParseError::InvalidKeyword(format!("Invalid distance '{}' for Bard - expected 'none' or a number", s))
                     })?)
                 };
                 Ok(BardStatement { distance }.into())
@@ -197,44 +368,51 @@ impl Role {
             Role::Confessor => match s.trim() {
                 "iamgood" => Ok(ConfessorStatement::IAmGood.into()),
                 "iamdizzy" => Ok(ConfessorStatement::IAmDizzy.into()),
-                _ => Err(format!(
+                _ => Err(StatementParseError::InvalidKeyword(format!(
                     "Invalid Confessor statement '{}' - expected 'iamgood' or 'iamdizzy'",
                     s
-                )),
+                ))),
             },
             Role::Dreamer => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Dreamer statement '{}' - expected format 'target_indexes;role'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
-                let target_index = s.trim().parse().map_err(|_| {
-                    format!("Invalid target index '{}' for Dreamer", s)
+                let target_index = parts[0].trim().parse().map_err(|_| {
+                    StatementParseError::InvalidIndex(format!("Invalid target index '{}' for Dreamer", parts[0]))
                 })?;
-                let role: Role = parts[1].trim().to_lowercase().parse().map_err(|e| {
-                    format!(
+                let named = parts[1].trim();
+                let (negated, named) = match named.strip_prefix('!') {
+                    Some(named) => (true, named),
+                    None => (false, named),
+                };
+                let role: Role = named.to_lowercase().parse().map_err(|e| {
+                    StatementParseError::InvalidRole(format!(
                         "Invalid target role '{}' in Dreamer statement: {}",
                         parts[1], e
-                    )
+                    ))
                 })?;
-                Ok(DreamerStatement { target_index, role }.into())
+                Ok(DreamerStatement { target_index, role, negated }.into())
             }
             Role::Druid => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Druid statement '{}' - expected format 'target_indexes;role'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_indexes = parse_indexes(parts[0])?;
                 let role: Role = parts[1].trim().to_lowercase().parse().map_err(|e| {
-                    format!(
+                    StatementParseError::InvalidRole(format!(
                         "Invalid target role '{}' in Druid statement: {}",
                         parts[1], e
-                    )
+                    ))
                 })?;
                 Ok(DruidStatement { target_indexes, role: Some(role) }.into())
             }
@@ -246,22 +424,23 @@ impl Role {
                 "clockwise" => Ok(EnlightenedStatement::Clockwise.into()),
                 "counterclockwise" => Ok(EnlightenedStatement::CounterClockwise.into()),
                 "equidistant" => Ok(EnlightenedStatement::Equidistant.into()),
-                _ => Err(format!(
+                _ => Err(StatementParseError::InvalidKeyword(format!(
                     "Invalid Enlightened statement '{}' - expected 'clockwise', 'counterclockwise', or 'equidistant'",
                     s
-                )),
+                ))),
             },
             Role::FortuneTeller => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Jester statement '{}' - expected format 'indexes;evil'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_indexes = parse_indexes(parts[0])?;
                 let is_evil: bool = parts[1].trim().parse().map_err(|_|{
-                    format!("Invalid bool '{}' for FortuneTeller", parts[1])
+                    StatementParseError::InvalidKeyword(format!("Invalid bool '{}' for FortuneTeller", parts[1]))
                 })?;
                 Ok(FortuneTellerStatement {
                     target_indexes,
@@ -270,27 +449,28 @@ impl Role {
             }
             Role::Gemcrafter => {
                 let target_index = s.trim().parse().map_err(|_| {
-                    format!("Invalid target index '{}' for Gemcrafter", s)
+                    StatementParseError::InvalidIndex(format!("Invalid target index '{}' for Gemcrafter", s))
                 })?;
                 Ok(GemcrafterStatement { target_index }.into())
             }
             Role::Hunter => {
                 let distance = s.trim().parse().map_err(|_| {
-                    format!("Invalid distance '{}' for Hunter", s)
+                    StatementParseError::InvalidKeyword(format!("Invalid distance '{}' for Hunter", s))
                 })?;
                 Ok(HunterStatement { distance }.into())
             }
             Role::Jester => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Jester statement '{}' - expected format 'indexes;evil_count'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_indexes = parse_indexes(parts[0])?;
                 let evil_count = parts[1].trim().parse().map_err(|_| {
-                    format!("Invalid evil count '{}' in Jester statement", parts[1])
+                    StatementParseError::InvalidKeyword(format!("Invalid evil count '{}' in Jester statement", parts[1]))
                 })?;
                 Ok(JesterStatement {
                     target_indexes,
@@ -300,22 +480,23 @@ impl Role {
             Role::Judge => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Judge statement '{}' - expected format 'target_index;truthy|lying'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_index = parts[0].trim().parse().map_err(|_| {
-                    format!("Invalid target index '{}' in Judge statement", parts[0])
+                    StatementParseError::InvalidIndex(format!("Invalid target index '{}' in Judge statement", parts[0]))
                 })?;
                 let is_lying = match parts[1].trim() {
                     "truthy" => false,
                     "lying" => true,
                     _ => {
-                        return Err(format!(
+                        return Err(StatementParseError::InvalidKeyword(format!(
                             "Invalid claim type '{}' in Judge statement - expected 'truthy' or 'lying'",
                             parts[1]
-                        ))
+                        )))
                     }
                 };
                 Ok(JudgeStatement {
@@ -325,53 +506,60 @@ impl Role {
             }
             Role::Knitter => {
                 let adjacent_count = s.trim().parse().map_err(|_| {
-                    format!("Invalid adjacent count '{}' for Knitter", s)
+                    StatementParseError::InvalidKeyword(format!("Invalid adjacent count '{}' for Knitter", s))
                 })?;
                 Ok(KnitterStatement { adjacent_count }.into())
             }
             Role::Lover => {
                 let evil_count = s.trim().parse().map_err(|_| {
-                    format!("Invalid evil count '{}' for Lover", s)
+                    StatementParseError::InvalidKeyword(format!("Invalid evil count '{}' for Lover", s))
                 })?;
                 Ok(LoverStatement { evil_count }.into())
             }
             Role::Medium => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Medium statement '{}' - expected format 'target_index;role'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_index = parts[0].trim().parse().map_err(|_| {
-                    format!("Invalid target index '{}' in Medium statement", parts[0])
+                    StatementParseError::InvalidIndex(format!("Invalid target index '{}' in Medium statement", parts[0]))
                 })?;
                 let role: Role = parts[1].trim().parse().map_err(|e| {
-                    format!(
+                    StatementParseError::InvalidRole(format!(
                         "Invalid target role '{}' in Medium statement: {}",
                         parts[1], e
-                    )
+                    ))
                 })?;
                 Ok(MediumStatement { target_index, role }.into())
             }
             Role::Oracle => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Oracle statement '{}' - expected format 'target_indexes;role'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_indexes = parse_indexes(parts[0])?;
-                let role: Role = parts[1].trim().to_lowercase().parse().map_err(|e| {
-                    format!(
-                        "Invalid target role '{}' in Oracle statement: {}",
-                        parts[1], e
-                    )
+                if target_indexes.count_ones() < 2 {
+                    return Err(StatementParseError::InvalidIndex(format!(
+                        "Oracle statement '{}' must name at least 2 targets",
+                        s
+                    )));
+                }
+                let claim = parts[1].parse().map_err(|e| {
+                    StatementParseError::InvalidRole(format!("Invalid claim '{}' in Oracle statement: {}", parts[1], e))
                 })?;
-                Ok(OracleStatement { target_indexes, role }.into())
+                Ok(OracleStatement { target_indexes, claim }.into())
+            }
+            Role::Baker | Role::Bombardier | Role::Knight | Role::Poet | Role::Wretch => {
+                self.parse_no_statement(s)
             }
-            Role::Poet => Ok(RoleStatement::NoStatement),
             Role::Scout => {
                 if s.to_lowercase() == "none" {
                     return Ok(ScoutStatement {role: None, distance:0}.into());
@@ -379,38 +567,40 @@ impl Role {
 
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Scout statement '{}' - expected format 'role;distance'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let role: Role = parts[0].trim().to_lowercase().parse().map_err(|e| {
-                    format!("Invalid role '{}' in Scout statement: {}", parts[0], e)
+                    StatementParseError::InvalidRole(format!("Invalid role '{}' in Scout statement: {}", parts[0], e))
                 })?;
                 let distance = parts[1].trim().parse().map_err(|_| {
-                    format!("Invalid distance '{}' in Scout statement", parts[1])
+                    StatementParseError::InvalidKeyword(format!("Invalid distance '{}' in Scout statement", parts[1]))
                 })?;
                 Ok(ScoutStatement { role: Some(role), distance }.into())
             }
             Role::Slayer => {
                 let parts: Vec<&str> = s.split(';').collect();
                 if parts.len() != 2 {
-                    return Err(format!(
-                        "Invalid Slayer statement '{}' - expected format 'target_index;good|evil'",
-                        s
-                    ));
+                    return Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    });
                 }
                 let target_index = parts[0].trim().parse().map_err(|_| {
-                    format!("Invalid target index '{}' in Slayer statement", parts[0])
+                    StatementParseError::InvalidIndex(format!("Invalid target index '{}' in Slayer statement", parts[0]))
                 })?;
                 let alignment = match parts[1].trim() {
                     "good" => Alignment::Good,
                     "evil" => Alignment::Evil,
                     _ => {
-                        return Err(format!(
+                        return Err(StatementParseError::InvalidKeyword(format!(
                             "Invalid alignment '{}' in Slayer statement - expected 'good' or 'evil'",
                             parts[1]
-                        ))
+                        )))
                     }
                 };
                 Ok(SlayerStatement {
@@ -419,31 +609,33 @@ impl Role {
                 }.into())
             }
             Role::PlagueDoctor => {
-                let target_indexes: Vec<usize> = s.split(';').map(|sv| sv.parse().map_err(|_| {
-                    format!("Invalid target index in PlagueDoctor statement")
-                })).collect::<Result<Vec<_>, _>>()?;
-                match target_indexes.len() {
-                    1 => Ok(PlagueDoctorStatement {
-                        corruption_index: target_indexes[0],
+                let parse = |sv: &str| sv.parse().map_err(|_| {
+                    StatementParseError::InvalidIndex(
+                        "Invalid target index in PlagueDoctor statement".to_string(),
+                    )
+                });
+                // Counted before parsing, so a bad seat in an extra part
+                // doesn't hide how many parts there are
+                let parts: Vec<&str> = s.split(';').collect();
+                match parts[..] {
+                    [corruption_index] => Ok(PlagueDoctorStatement {
+                        corruption_index: parse(corruption_index)?,
                         evil_index: None,
                     }.into()),
-                    2 => Ok(PlagueDoctorStatement {
-                        corruption_index: target_indexes[1],
-                        evil_index: Some(target_indexes[0]),
+                    // Same order as the in-game text, the evil seat first
+                    [evil_index, corruption_index] => Ok(PlagueDoctorStatement {
+                        corruption_index: parse(corruption_index)?,
+                        evil_index: Some(parse(evil_index)?),
                     }.into()),
-                    _ => Err(format!(
-                        "PlagueDoctor must have 1 or 2 target indexes, got {} in '{}'",
-                        target_indexes.len(),
-                        s
-                    )),
+                    _ => Err(StatementParseError::WrongArgCount {
+                        expected: 2,
+                        got: parts.len(),
+                        input: s.to_string(),
+                    }),
                 }
             }
-            Role::Baker
-            | Role::Bombardier
-            | Role::Knight
-            | Role::DoppelGanger
+            Role::DoppelGanger
             | Role::Drunk
-            | Role::Wretch
             | Role::Baa
             | Role::Lilis
             | Role::Minion
@@ -455,13 +647,23 @@ impl Role {
             | Role::TwinMinion
             | Role::Witch
             | Role::Counsellor
-            | Role::Witness => Err(format!(
-                "No statement parsing implemented for {:?}",
-                self
-            )),
+            | Role::Witness => Err(StatementParseError::UnsupportedRole(*self)),
         }
     }
-    pub fn parse_natural_statement(&self, s: &str) -> Result<RoleStatement, String> {
+    /// Same as `parse_statement`, but seats are numbered from 1 like in the
+    /// game and in the solver's output
+    pub fn parse_statement_one_based(&self, s: &str) -> Result<RoleStatement, StatementParseError> {
+        self.parse_statement(s)?.seats_from_one_based()
+    }
+    /// For roles that never say anything, only an empty statement is taken
+    fn parse_no_statement(&self, s: &str) -> Result<RoleStatement, StatementParseError> {
+        if s.trim().is_empty() {
+            Ok(RoleStatement::NoStatement)
+        } else {
+            Err(StatementParseError::UnexpectedStatement(*self))
+        }
+    }
+    pub fn parse_natural_statement(&self, s: &str) -> Result<RoleStatement, StatementParseError> {
         match self {
             Role::Alchemist => {
                 if let Some(caps) = regex::Regex::new(r"I cured (\d+) Corruptions?")
@@ -469,11 +671,17 @@ impl Role {
                     .captures(s)
                 {
                     let corrupt_count = caps[1].parse().map_err(|_| {
-                        format!("Invalid corrupt count in Alchemist statement '{}'", s)
+                        StatementParseError::InvalidKeyword(format!(
+                            "Invalid corrupt count in Alchemist statement '{}'",
+                            s
+                        ))
                     })?;
                     Ok(AlchemistStatement { corrupt_count }.into())
                 } else {
-                    Err(format!("Invalid Alchemist statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Alchemist statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::Architect => {
@@ -486,25 +694,33 @@ impl Role {
                         "right" => Ok(ArchitectStatement::Right.into()),
                         "left" => Ok(ArchitectStatement::Left.into()),
                         "equal" => Ok(ArchitectStatement::Equal.into()),
-                        _ => Err(format!(
+                        _ => Err(StatementParseError::InvalidKeyword(format!(
                             "Invalid Architect statement '{}' - expected 'left', 'right', or 'equal'",
                             s
-                        )),
+                        ))),
                     }
                 } else {
-                    Err(format!("invalid bard statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "invalid bard statement '{}'",
+                        s
+                    )))
                 }
             }
-            Role::Baker => Ok(RoleStatement::NoStatement),
+            Role::Baker | Role::Bombardier | Role::Knight | Role::Poet | Role::Wretch => {
+                self.parse_no_statement(s)
+            }
             Role::Bard => {
                 let s = s.to_lowercase();
                 if let Some(caps) = regex::Regex::new(r"i am (\d+) cards?")
                     .unwrap()
                     .captures(&s)
                 {
-                    let distance = caps[1]
-                        .parse()
-                        .map_err(|_| format!("invalid distance in bard statement '{}'", s))?;
+                    let distance = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidKeyword(format!(
+                            "invalid distance in bard statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(BardStatement {
                         distance: Some(distance),
                     }
@@ -512,54 +728,46 @@ impl Role {
                 } else if s.trim() == "there are no corrupted characters" {
                     Ok(BardStatement { distance: None }.into())
                 } else {
-                    Err(format!("invalid bard statement '{}' - expected format like 'i am 2 cards away from corrupted' or 'none'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("invalid bard statement '{}' - expected format like 'i am 2 cards away from corrupted' or 'none'", s)))
                 }
             }
             Role::Bishop => {
-                if let Some(caps) = regex::Regex::new(r"#(\d+)[^#]+#(\d+)[^#]+(\d+)?")
+                let target_indexes = regex::Regex::new(r"#(\d+)")
                     .unwrap()
-                    .captures(s)
-                {
-                    let mut target_indexes = Vec::new();
-                    for i in 1..=3 {
-                        if let Some(m) = caps.get(i) {
-                            let idx = m.as_str().parse::<usize>().map_err(|_| {
-                                format!("Invalid index in Bishop statement '{}'", s)
-                            })?;
-                            target_indexes.push(idx - 1);
-                        }
-                    }
-                    if target_indexes.is_empty() {
-                        return Err(format!(
-                            "No valid indexes found in Bishop statement '{}'",
-                            s
-                        ));
-                    }
-                    let mut bits = TargetIndexes::default();
-                    for idx in target_indexes {
-                        bits.set(idx, true);
-                    }
-
-                    Ok(BishopStatement {
-                        target_indexes: bits,
-                    }
-                    .into())
-                } else {
-                    Err(format!(
+                    .captures_iter(s)
+                    .map(|caps| {
+                        caps[1]
+                            .parse::<usize>()
+                            .map_err(|_| {
+                                StatementParseError::InvalidIndex(format!(
+                                    "Invalid index in Bishop statement '{}'",
+                                    s
+                                ))
+                            })
+                            .and_then(|idx| seat_from_natural(idx, s))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if target_indexes.len() < 2 {
+                    return Err(StatementParseError::InvalidKeyword(format!(
                         "Invalid Bishop statement '{}' - expected format like #8 #1 #7'",
                         s
-                    ))
+                    )));
+                }
+
+                Ok(BishopStatement {
+                    target_indexes: to_bitvec(target_indexes),
                 }
+                .into())
             }
             Role::Confessor => {
                 let s = s.trim().to_lowercase();
                 match s.as_str() {
                 "i am dizzy" | "i'm dizzy" | "iamdizzy" => Ok(ConfessorStatement::IAmDizzy.into()),
                 "i am good" | "i'm good" | "iamgood" => Ok(ConfessorStatement::IAmGood.into()),
-                _ => Err(format!(
+                _ => Err(StatementParseError::InvalidKeyword(format!(
                     "Invalid Confessor statement '{}' - expected something like 'I am dizzy' or 'I am good'",
                     s
-                )),
+                ))),
             }
             }
             Role::Medium => {
@@ -567,18 +775,25 @@ impl Role {
                     .unwrap()
                     .captures(s)
                 {
-                    let target_index = caps[1]
-                        .parse::<usize>()
-                        .map_err(|_| format!("Invalid target index in Medium statement '{}'", s))?;
-                    let role = Role::from_str(&caps[2].to_lowercase())
-                        .map_err(|_| format!("Invalid role '{}' in Medium statement", &caps[2]))?;
+                    let target_index = caps[1].parse::<usize>().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid target index in Medium statement '{}'",
+                            s
+                        ))
+                    })?;
+                    let role = Role::from_str(&caps[2].to_lowercase()).map_err(|_| {
+                        StatementParseError::InvalidRole(format!(
+                            "Invalid role '{}' in Medium statement",
+                            &caps[2]
+                        ))
+                    })?;
                     Ok(MediumStatement {
                         target_index: target_index - 1,
                         role,
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Medium statement '{}' - expected format like '#4 is a real Hunter'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Medium statement '{}' - expected format like '#4 is a real Hunter'", s)))
                 }
             }
             Role::Gemcrafter => {
@@ -587,17 +802,20 @@ impl Role {
                     .captures(s)
                 {
                     let target_index = caps[1].parse::<usize>().map_err(|_| {
-                        format!("Invalid target index in Gemcrafter statement '{}'", s)
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid target index in Gemcrafter statement '{}'",
+                            s
+                        ))
                     })?;
                     Ok(GemcrafterStatement {
                         target_index: target_index - 1,
                     }
                     .into())
                 } else {
-                    Err(format!(
+                    Err(StatementParseError::InvalidKeyword(format!(
                         "Invalid Gemcrafter statement '{}' - expected format like '#5 is Good'",
                         s
-                    ))
+                    )))
                 }
             }
             Role::Hunter => {
@@ -606,12 +824,15 @@ impl Role {
                         .unwrap()
                         .captures(s)
                 {
-                    let distance = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid distance in Hunter statement '{}'", s))?;
+                    let distance = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidKeyword(format!(
+                            "Invalid distance in Hunter statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(HunterStatement { distance }.into())
                 } else {
-                    Err(format!("Invalid Hunter statement '{}' - expe|ted format like 'I am 2 cards away from closest Evil'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Hunter statement '{}' - expe|ted format like 'I am 2 cards away from closest Evil'", s)))
                 }
             }
             Role::Enlightened => {
@@ -625,10 +846,13 @@ impl Role {
                         "Clockwise" => Ok(EnlightenedStatement::Clockwise.into()),
                         "Counter-clockwise" => Ok(EnlightenedStatement::CounterClockwise.into()),
                         "equidistant" => Ok(EnlightenedStatement::Equidistant.into()),
-                        _ => Err(format!("Invalid Enlightened statement '{}'", s)),
+                        _ => Err(StatementParseError::InvalidKeyword(format!(
+                            "Invalid Enlightened statement '{}'",
+                            s
+                        ))),
                     }
                 } else {
-                    Err(format!("Invalid Enlightened statement '{}' - expected format like 'Closest Evil is: Clockwise'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Enlightened statement '{}' - expected format like 'Closest Evil is: Clockwise'", s)))
                 }
             }
             Role::Judge => {
@@ -636,13 +860,21 @@ impl Role {
                     .unwrap()
                     .captures(s)
                 {
-                    let target_index = caps[1]
-                        .parse::<usize>()
-                        .map_err(|_| format!("Invalid target index in Judge statement '{}'", s))?;
+                    let target_index = caps[1].parse::<usize>().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid target index in Judge statement '{}'",
+                            s
+                        ))
+                    })?;
                     let is_lying = match &caps[2] {
                         "Truth" => false,
                         "Lying" => true,
-                        _ => return Err(format!("Invalid claim type in Judge statement '{}'", s)),
+                        _ => {
+                            return Err(StatementParseError::InvalidKeyword(format!(
+                                "Invalid claim type in Judge statement '{}'",
+                                s
+                            )))
+                        }
                     };
                     Ok(JudgeStatement {
                         target_index: target_index - 1,
@@ -650,40 +882,37 @@ impl Role {
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Judge statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Judge statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::Empress => {
                 if let Some(caps) =
-                    regex::Regex::new(r"One is Evil:\s*#(\d+)(?:\s*,\s*#(\d+))?(?:\s*or\s*#(\d+))?")
+                    regex::Regex::new(r"One is Evil:\s*((?:#\d+(?:\s*,\s*|\s+or\s+))*#\d+)")
                         .unwrap()
                         .captures(s)
                 {
                     let mut indexes = Vec::new();
-                    for i in 1..=3 {
-                        if let Some(m) = caps.get(i) {
-                            let idx = m.as_str().parse::<usize>().map_err(|_| {
-                                format!("Invalid index in Empress statement '{}'", s)
-                            })?;
-                            indexes.push(idx - 1);
-                        }
-                    }
-                    if indexes.is_empty() {
-                        return Err(format!(
-                            "No valid indexes found in Empress statement '{}'",
-                            s
-                        ));
-                    }
-                    let mut bits = TargetIndexes::default();
-                    for idx in indexes {
-                        bits.set(idx, true);
+                    for m in regex::Regex::new(r"#(\d+)")
+                        .unwrap()
+                        .captures_iter(&caps[1])
+                    {
+                        let idx: usize = m[1].parse().map_err(|_| {
+                            StatementParseError::InvalidIndex(format!(
+                                "Invalid index in Empress statement '{}'",
+                                s
+                            ))
+                        })?;
+                        indexes.push(idx - 1);
                     }
                     Ok(EmpressStatement {
-                        target_indexes: bits,
+                        target_indexes: to_bitvec(indexes),
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Empress statement '{}' - expected format like 'One is Evil: #8, #1 or #7'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Empress statement '{}' - expected format like 'One is Evil: #8, #1 or #7'", s)))
                 }
             }
             Role::Lover => {
@@ -693,12 +922,15 @@ impl Role {
                     .unwrap()
                     .captures(s)
                 {
-                    let evil_count = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid evil count in Lover statement '{}'", s))?;
+                    let evil_count = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidKeyword(format!(
+                            "Invalid evil count in Lover statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(LoverStatement { evil_count }.into())
                 } else {
-                    Err(format!("Invalid Lover statement '{}' - expected format like 'NO Evils adjacent to me' or '2 Evils adjacent to me'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Lover statement '{}' - expected format like 'NO Evils adjacent to me' or '2 Evils adjacent to me'", s)))
                 }
             }
             Role::Scout => {
@@ -709,18 +941,25 @@ impl Role {
                     }
                     .into())
                 } else if let Some(caps) = regex::Regex::new(r"(\w+).*(\d+)").unwrap().captures(s) {
-                    let role = Role::from_str(&caps[1].to_lowercase())
-                        .map_err(|_| format!("Invalid role '{}' in Scout statement", &caps[1]))?;
-                    let distance = caps[2]
-                        .parse()
-                        .map_err(|_| format!("Invalid distance in Scout statement '{}'", s))?;
+                    let role = Role::from_str(&caps[1].to_lowercase()).map_err(|_| {
+                        StatementParseError::InvalidRole(format!(
+                            "Invalid role '{}' in Scout statement",
+                            &caps[1]
+                        ))
+                    })?;
+                    let distance = caps[2].parse().map_err(|_| {
+                        StatementParseError::InvalidKeyword(format!(
+                            "Invalid distance in Scout statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(ScoutStatement {
                         role: Some(role),
                         distance,
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Scout statement '{}' - expected format like 'Minion is 1 card away from closest Evil'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Scout statement '{}' - expected format like 'Minion is 1 card away from closest Evil'", s)))
                 }
             }
             Role::Knitter => {
@@ -728,16 +967,23 @@ impl Role {
                     .eq_ignore_ascii_case("Evils are not adjacent to eachother")
                 {
                     Ok(KnitterStatement { adjacent_count: 0 }.into())
-                } else if let Some(caps) = regex::Regex::new(r"(\d+)").unwrap().captures(s) {
-                    let adjacent_count = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid evil count in Knitter statement '{}'", s))?;
+                } else if let Some(caps) =
+                    regex::Regex::new(r"(?i)^There (?:is|are) (\d+) Evil pairs?$")
+                        .unwrap()
+                        .captures(s.trim())
+                {
+                    let adjacent_count = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidKeyword(format!(
+                            "Invalid evil count in Knitter statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(KnitterStatement { adjacent_count }.into())
                 } else {
-                    Err(format!(
-                        "Invalid Knitter statement '{}' - expected format",
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Knitter statement '{}' - expected format like 'There are 2 Evil pairs' or 'Evils are not adjacent to eachother'",
                         s
-                    ))
+                    )))
                 }
             }
             Role::PlagueDoctor => {
@@ -752,17 +998,20 @@ impl Role {
                             // - corruption_index is the second part
                             // - evil_index is the first part
                             let corruption_index: usize = caps[2].parse().map_err(|_| {
-                                format!("invalid second index in plague doctor statement '{}'", s)
+                                StatementParseError::InvalidIndex(format!(
+                                    "invalid second index in plague doctor statement '{}'",
+                                    s
+                                ))
                             })?;
                             let corruption_index = corruption_index - 1;
 
                             let evil_index: usize = caps[1]
                                 .parse::<usize>()
                                 .map_err(|_| {
-                                    format!(
+                                    StatementParseError::InvalidIndex(format!(
                                         "invalid first index in plague doctor statement '{}'",
                                         s
-                                    )
+                                    ))
                                 })
                                 .map(|x| x - 1)?;
 
@@ -771,7 +1020,10 @@ impl Role {
                         None => {
                             // If there's only one part, it's corruption_index and evil_index is None
                             let corruption_index: usize = caps[1].parse().map_err(|_| {
-                                format!("invalid index in plague doctor statement '{}'", s)
+                                StatementParseError::InvalidIndex(format!(
+                                    "invalid index in plague doctor statement '{}'",
+                                    s
+                                ))
                             })?;
                             let corruption_index = corruption_index - 1;
 
@@ -784,7 +1036,10 @@ impl Role {
                     }
                     .into())
                 } else {
-                    Err(format!("invalid plague doctor statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "invalid plague doctor statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::FortuneTeller => {
@@ -792,12 +1047,18 @@ impl Role {
                     .unwrap()
                     .captures(s)
                 {
-                    let first: usize = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid index in FortuneTeller statement '{}'", s))?;
-                    let second: usize = caps[2]
-                        .parse()
-                        .map_err(|_| format!("Invalid index in FortuneTeller statement '{}'", s))?;
+                    let first: usize = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid index in FortuneTeller statement '{}'",
+                            s
+                        ))
+                    })?;
+                    let second: usize = caps[2].parse().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid index in FortuneTeller statement '{}'",
+                            s
+                        ))
+                    })?;
                     let is_evil = caps[3] == *"True";
                     let target_indexes = to_bitvec(vec![first - 1, second - 1]);
                     Ok(FortuneTellerStatement {
@@ -806,89 +1067,111 @@ impl Role {
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Scout statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Scout statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::Jester => {
-                if let Some(caps) = regex::Regex::new(r"#(\d+).*#(\d+).*#(\d+).*(\d+) Evils?")
-                    .unwrap()
-                    .captures(s)
+                // The clue names two or three seats before the count
+                if let Some(caps) =
+                    regex::Regex::new(r"#(\d+)\D*#(\d+)(?:\D*#(\d+))?\D*(\d+) Evils?")
+                        .unwrap()
+                        .captures(s)
                 {
                     let mut indexes = Vec::new();
                     for i in 1..=3 {
                         if let Some(m) = caps.get(i) {
                             let idx: usize = m.as_str().parse().map_err(|_| {
-                                format!("Invalid index in Empress statement '{}'", s)
+                                StatementParseError::InvalidIndex(format!(
+                                    "Invalid index in Jester statement '{}'",
+                                    s
+                                ))
                             })?;
-                            indexes.push(idx - 1);
+                            indexes.push(seat_from_natural(idx, s)?);
                         }
                     }
                     let target_indexes = to_bitvec(indexes);
-                    let evil_count: usize = caps[4]
-                        .parse()
-                        .map_err(|_| format!("Invalid index in Empress statement '{}'", s))?;
+                    let evil_count: usize = caps[4].parse().map_err(|_| {
+                        StatementParseError::InvalidKeyword(format!(
+                            "Invalid evil count in Jester statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(JesterStatement {
                         target_indexes,
                         evil_count,
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Empress statement '{}' - expected format like 'One is Evil: #8, #1 or #7'", s))
+                    Err(StatementParseError::InvalidKeyword(format!("Invalid Jester statement '{}' - expected format like '#1, #3 and #5: 1 Evil'", s)))
                 }
             }
             Role::Oracle => {
-                if let Some(caps) = regex::Regex::new(r"#(\d+) or #(\d+) is a (\w+)")
-                    .unwrap()
-                    .captures(s)
+                if let Some(caps) =
+                    regex::Regex::new(r"((?:#\d+(?:\s*,\s*|\s+or\s+))+#\d+) is an? (\w+)")
+                        .unwrap()
+                        .captures(s)
                 {
                     let mut indexes = Vec::new();
-                    for i in 1..=2 {
-                        if let Some(m) = caps.get(i) {
-                            let idx: usize = m.as_str().parse().map_err(|_| {
-                                format!("Invalid index in Oracle statement '{}'", s)
-                            })?;
-                            indexes.push(idx - 1);
-                        }
+                    for m in regex::Regex::new(r"#(\d+)")
+                        .unwrap()
+                        .captures_iter(&caps[1])
+                    {
+                        let idx: usize = m[1].parse().map_err(|_| {
+                            StatementParseError::InvalidIndex(format!(
+                                "Invalid index in Oracle statement '{}'",
+                                s
+                            ))
+                        })?;
+                        indexes.push(idx - 1);
                     }
                     let target_indexes = to_bitvec(indexes);
-                    let role: Role = caps[3].trim().to_lowercase().parse().map_err(|e| {
-                        format!(
-                            "Invalid target role '{}' in Oracle statement: {}",
-                            &caps[3], e
-                        )
+                    let claim = caps[2].parse().map_err(|e| {
+                        StatementParseError::InvalidRole(format!(
+                            "Invalid claim '{}' in Oracle statement: {}",
+                            &caps[2], e
+                        ))
                     })?;
 
                     Ok(OracleStatement {
                         target_indexes,
-                        role,
+                        claim,
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Oracle statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Oracle statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::Druid => {
+                // Either clue names two or three seats
                 if let Some(caps) =
-                    regex::Regex::new(r"Among #(\d+), #(\d+), #(\d+) there is: (\w+)")
+                    regex::Regex::new(r"Among #(\d+), #(\d+)(?:, #(\d+))? there is: (\w+)")
                         .unwrap()
                         .captures(s)
                 {
                     let mut indexes = Vec::new();
                     for i in 1..=3 {
                         if let Some(m) = caps.get(i) {
-                            let idx: usize = m
-                                .as_str()
-                                .parse()
-                                .map_err(|_| format!("Invalid index in Druid statement '{}'", s))?;
-                            indexes.push(idx - 1);
+                            let idx: usize = m.as_str().parse().map_err(|_| {
+                                StatementParseError::InvalidIndex(format!(
+                                    "Invalid index in Druid statement '{}'",
+                                    s
+                                ))
+                            })?;
+                            indexes.push(seat_from_natural(idx, s)?);
                         }
                     }
                     let target_indexes = to_bitvec(indexes);
                     let role: Role = caps[4].trim().to_lowercase().parse().map_err(|e| {
-                        format!(
+                        StatementParseError::InvalidRole(format!(
                             "Invalid target role '{}' in Druid statement: {}",
                             &caps[4], e
-                        )
+                        ))
                     })?;
 
                     Ok(DruidStatement {
@@ -897,18 +1180,20 @@ impl Role {
                     }
                     .into())
                 } else if let Some(caps) =
-                    regex::Regex::new(r"Among #(\d+), #(\d+), #(\d+) there are NO Outcasts")
+                    regex::Regex::new(r"Among #(\d+), #(\d+)(?:, #(\d+))? there are NO Outcasts")
                         .unwrap()
                         .captures(s)
                 {
                     let mut indexes = Vec::new();
                     for i in 1..=3 {
                         if let Some(m) = caps.get(i) {
-                            let idx: usize = m
-                                .as_str()
-                                .parse()
-                                .map_err(|_| format!("Invalid index in Druid statement '{}'", s))?;
-                            indexes.push(idx - 1);
+                            let idx: usize = m.as_str().parse().map_err(|_| {
+                                StatementParseError::InvalidIndex(format!(
+                                    "Invalid index in Druid statement '{}'",
+                                    s
+                                ))
+                            })?;
+                            indexes.push(seat_from_natural(idx, s)?);
                         }
                     }
                     let target_indexes = to_bitvec(indexes);
@@ -919,7 +1204,10 @@ impl Role {
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Druid statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Druid statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::Slayer => {
@@ -927,11 +1215,14 @@ impl Role {
                     .unwrap()
                     .captures(s)
                 {
-                    let target_index: usize = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid index in Slauer statement '{}'", s))?;
+                    let target_index: usize = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid index in Slayer statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(SlayerStatement {
-                        target_index,
+                        target_index: seat_from_natural(target_index, s)?,
                         alignment: Alignment::Evil,
                     }
                     .into())
@@ -939,51 +1230,111 @@ impl Role {
                     .unwrap()
                     .captures(s)
                 {
-                    let target_index: usize = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid index in Slayer statement '{}'", s))?;
+                    let target_index: usize = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid index in Slayer statement '{}'",
+                            s
+                        ))
+                    })?;
                     Ok(SlayerStatement {
-                        target_index,
+                        target_index: seat_from_natural(target_index, s)?,
                         alignment: Alignment::Good,
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Slayer statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Slayer statement '{}'",
+                        s
+                    )))
                 }
             }
             Role::Dreamer => {
-                if let Some(caps) = regex::Regex::new(r"#(\d+) could be: (\w+)")
+                if let Some(caps) = regex::Regex::new(r"#(\d+) could (not )?be: (\w+)")
                     .unwrap()
                     .captures(s)
                 {
-                    let target_index: usize = caps[1]
-                        .parse()
-                        .map_err(|_| format!("Invalid index in Dreamer statement '{}'", s))?;
-                    let role: Role = caps[2].trim().to_lowercase().parse().map_err(|e| {
-                        format!(
+                    let target_index: usize = caps[1].parse().map_err(|_| {
+                        StatementParseError::InvalidIndex(format!(
+                            "Invalid index in Dreamer statement '{}'",
+                            s
+                        ))
+                    })?;
+                    let role: Role = caps[3].trim().to_lowercase().parse().map_err(|e| {
+                        StatementParseError::InvalidRole(format!(
                             "Invalid target role '{}' in Dreamer statement: {}",
-                            &caps[2], e
-                        )
+                            &caps[3], e
+                        ))
                     })?;
 
                     Ok(DreamerStatement {
-                        target_index: target_index - 1,
+                        target_index: seat_from_natural(target_index, s)?,
                         role,
+                        negated: caps.get(2).is_some(),
                     }
                     .into())
                 } else {
-                    Err(format!("Invalid Oracle statement '{}'", s))
+                    Err(StatementParseError::InvalidKeyword(format!(
+                        "Invalid Dreamer statement '{}'",
+                        s
+                    )))
                 }
             }
-            Role::Poet => Ok(RoleStatement::NoStatement),
-            _ => Err(format!(
-                "No natural statement parsing implemented for {:?}",
-                self
-            )),
+            _ => Err(StatementParseError::UnsupportedRole(*self)),
         }
     }
 }
 
+/// Why a statement couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementParseError {
+    /// The role doesn't make statements the parser can read
+    UnsupportedRole(Role),
+    /// The role never makes a statement, but was given one
+    UnexpectedStatement(Role),
+    /// The typed statement in `input` has the wrong number of `;`-separated
+    /// parts. For statements that take a varying number, `expected` is the most
+    /// they take.
+    WrongArgCount {
+        expected: usize,
+        got: usize,
+        input: String,
+    },
+    /// A seat that isn't a number, doesn't exist or is missing
+    InvalidIndex(String),
+    /// A role, or an Oracle's claim, that isn't one
+    InvalidRole(String),
+    /// Anything else the role doesn't say, e.g. a keyword, count or distance,
+    /// or a statement not in the expected wording
+    InvalidKeyword(String),
+}
+
+impl fmt::Display for StatementParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatementParseError::UnsupportedRole(role) => {
+                write!(f, "No statement parsing implemented for {:?}", role)
+            }
+            StatementParseError::UnexpectedStatement(role) => {
+                write!(f, "{} makes no statement; use '?'", role.display_name())
+            }
+            StatementParseError::WrongArgCount {
+                expected,
+                got,
+                input,
+            } => write!(
+                f,
+                "Invalid statement '{}' - expected {} parts separated by ';', got {}",
+                input, expected, got
+            ),
+            StatementParseError::InvalidIndex(message)
+            | StatementParseError::InvalidRole(message)
+            | StatementParseError::InvalidKeyword(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for StatementParseError {}
+
 macro_rules! role_statements {
     (
         $(
@@ -1043,6 +1394,257 @@ role_statements! {
     PlagueDoctor(PlagueDoctorStatement),
 }
 
+impl RoleStatement {
+    /// The specific role the statement is about, if any. Other statements only
+    /// care about seats' alignment or group, not which role they are.
+    pub fn named_role(&self) -> Option<Role> {
+        match self {
+            RoleStatement::Dreamer(DreamerStatement { role, .. })
+            | RoleStatement::Medium(MediumStatement { role, .. })
+            | RoleStatement::Oracle(OracleStatement {
+                claim: OracleClaim::Role(role),
+                ..
+            }) => Some(*role),
+            RoleStatement::Druid(DruidStatement { role, .. })
+            | RoleStatement::Scout(ScoutStatement { role, .. }) => *role,
+            _ => None,
+        }
+    }
+
+    /// The statement in the typed format `Role::parse_statement` reads, with
+    /// seats numbered from 0. That format is the canonical input form, `Display`
+    /// is prose for reading and isn't meant to be parsed back. `None` for the
+    /// statements the typed format can't express: a Druid naming no role, and
+    /// a Scout's "only 1 Evil" with a distance other than 0, since `none`
+    /// always reads back with distance 0.
+    pub fn to_input_string(&self) -> Option<String> {
+        let seats = |target_indexes: &TargetIndexes| target_indexes.iter_ones().join(",");
+        Some(match self {
+            RoleStatement::NoStatement => String::new(),
+            RoleStatement::Alchemist(AlchemistStatement { corrupt_count }) => {
+                corrupt_count.to_string()
+            }
+            RoleStatement::Architect(statement) => match statement {
+                ArchitectStatement::Left => "left",
+                ArchitectStatement::Right => "right",
+                ArchitectStatement::Equal => "equal",
+            }
+            .to_string(),
+            RoleStatement::Bard(BardStatement { distance }) => {
+                distance.map_or("none".to_string(), |distance| distance.to_string())
+            }
+            RoleStatement::Bishop(BishopStatement { target_indexes })
+            | RoleStatement::Empress(EmpressStatement { target_indexes }) => seats(target_indexes),
+            RoleStatement::Confessor(statement) => match statement {
+                ConfessorStatement::IAmGood => "iamgood",
+                ConfessorStatement::IAmDizzy => "iamdizzy",
+            }
+            .to_string(),
+            RoleStatement::Druid(DruidStatement {
+                target_indexes,
+                role,
+            }) => format!("{};{}", seats(target_indexes), (*role)?),
+            RoleStatement::Dreamer(DreamerStatement {
+                target_index,
+                role,
+                negated,
+            }) => format!(
+                "{};{}{}",
+                target_index,
+                if *negated { "!" } else { "" },
+                role
+            ),
+            RoleStatement::Enlightened(statement) => match statement {
+                EnlightenedStatement::Clockwise => "clockwise",
+                EnlightenedStatement::CounterClockwise => "counterclockwise",
+                EnlightenedStatement::Equidistant => "equidistant",
+            }
+            .to_string(),
+            RoleStatement::FortuneTeller(FortuneTellerStatement {
+                target_indexes,
+                is_evil,
+            }) => format!("{};{}", seats(target_indexes), is_evil),
+            RoleStatement::Gemcrafter(GemcrafterStatement { target_index }) => {
+                target_index.to_string()
+            }
+            RoleStatement::Hunter(HunterStatement { distance }) => distance.to_string(),
+            RoleStatement::Jester(JesterStatement {
+                target_indexes,
+                evil_count,
+            }) => format!("{};{}", seats(target_indexes), evil_count),
+            RoleStatement::Judge(JudgeStatement {
+                target_index,
+                is_lying,
+            }) => format!(
+                "{};{}",
+                target_index,
+                if *is_lying { "lying" } else { "truthy" }
+            ),
+            RoleStatement::Knitter(KnitterStatement { adjacent_count }) => {
+                adjacent_count.to_string()
+            }
+            RoleStatement::Lover(LoverStatement { evil_count }) => evil_count.to_string(),
+            RoleStatement::Medium(MediumStatement { target_index, role }) => {
+                format!("{};{}", target_index, role)
+            }
+            RoleStatement::Oracle(OracleStatement {
+                target_indexes,
+                claim,
+            }) => {
+                let claim = match claim {
+                    OracleClaim::Role(role) => role.to_string(),
+                    OracleClaim::Group(group) => format!("group:{:?}", group).to_lowercase(),
+                };
+                format!("{};{}", seats(target_indexes), claim)
+            }
+            RoleStatement::Scout(ScoutStatement { role, distance }) => match role {
+                Some(role) => format!("{};{}", role, distance),
+                None if *distance == 0 => "none".to_string(),
+                None => return None,
+            },
+            RoleStatement::Slayer(SlayerStatement {
+                target_index,
+                alignment,
+            }) => format!(
+                "{};{}",
+                target_index,
+                match alignment {
+                    Alignment::Good => "good",
+                    Alignment::Evil => "evil",
+                }
+            ),
+            RoleStatement::PlagueDoctor(PlagueDoctorStatement {
+                corruption_index,
+                evil_index,
+            }) => match evil_index {
+                Some(evil_index) => format!("{};{}", evil_index, corruption_index),
+                None => corruption_index.to_string(),
+            },
+        })
+    }
+
+    /// Moves every seat the statement refers to down by one, failing on seat 0
+    fn seats_from_one_based(self) -> Result<RoleStatement, StatementParseError> {
+        self.map_seats(|i: usize| {
+            i.checked_sub(1).ok_or_else(|| {
+                StatementParseError::InvalidIndex(
+                    "Seat 0 doesn't exist, seats are numbered from 1".to_string(),
+                )
+            })
+        })
+    }
+
+    /// Moves every seat the statement refers to up by `offset`, e.g. 1 to show
+    /// it with seats numbered like in the game. `None` if a statement naming
+    /// several seats would name one past the 16 it can hold.
+    pub fn offset_seats(self, offset: usize) -> Option<RoleStatement> {
+        self.map_seats(|i| Ok(i + offset)).ok()
+    }
+
+    fn map_seats(
+        self,
+        seat: impl Fn(usize) -> Result<usize, StatementParseError>,
+    ) -> Result<RoleStatement, StatementParseError> {
+        let seats = |target_indexes: TargetIndexes| -> Result<TargetIndexes, StatementParseError> {
+            try_to_bitvec(
+                target_indexes
+                    .iter_ones()
+                    .map(&seat)
+                    .collect::<Result<_, _>>()?,
+            )
+        };
+
+        Ok(match self {
+            RoleStatement::Bishop(BishopStatement { target_indexes }) => BishopStatement {
+                target_indexes: seats(target_indexes)?,
+            }
+            .into(),
+            RoleStatement::Druid(DruidStatement {
+                target_indexes,
+                role,
+            }) => DruidStatement {
+                target_indexes: seats(target_indexes)?,
+                role,
+            }
+            .into(),
+            RoleStatement::Empress(EmpressStatement { target_indexes }) => EmpressStatement {
+                target_indexes: seats(target_indexes)?,
+            }
+            .into(),
+            RoleStatement::FortuneTeller(FortuneTellerStatement {
+                target_indexes,
+                is_evil,
+            }) => FortuneTellerStatement {
+                target_indexes: seats(target_indexes)?,
+                is_evil,
+            }
+            .into(),
+            RoleStatement::Jester(JesterStatement {
+                target_indexes,
+                evil_count,
+            }) => JesterStatement {
+                target_indexes: seats(target_indexes)?,
+                evil_count,
+            }
+            .into(),
+            RoleStatement::Oracle(OracleStatement {
+                target_indexes,
+                claim,
+            }) => OracleStatement {
+                target_indexes: seats(target_indexes)?,
+                claim,
+            }
+            .into(),
+            RoleStatement::Dreamer(DreamerStatement {
+                target_index,
+                role,
+                negated,
+            }) => DreamerStatement {
+                target_index: seat(target_index)?,
+                role,
+                negated,
+            }
+            .into(),
+            RoleStatement::Gemcrafter(GemcrafterStatement { target_index }) => {
+                GemcrafterStatement {
+                    target_index: seat(target_index)?,
+                }
+                .into()
+            }
+            RoleStatement::Judge(JudgeStatement {
+                target_index,
+                is_lying,
+            }) => JudgeStatement {
+                target_index: seat(target_index)?,
+                is_lying,
+            }
+            .into(),
+            RoleStatement::Medium(MediumStatement { target_index, role }) => MediumStatement {
+                target_index: seat(target_index)?,
+                role,
+            }
+            .into(),
+            RoleStatement::Slayer(SlayerStatement {
+                target_index,
+                alignment,
+            }) => SlayerStatement {
+                target_index: seat(target_index)?,
+                alignment,
+            }
+            .into(),
+            RoleStatement::PlagueDoctor(PlagueDoctorStatement {
+                corruption_index,
+                evil_index,
+            }) => PlagueDoctorStatement {
+                corruption_index: seat(corruption_index)?,
+                evil_index: evil_index.map(&seat).transpose()?,
+            }
+            .into(),
+            other => other,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlchemistStatement {
     pub corrupt_count: usize,
@@ -1095,16 +1697,41 @@ pub struct BishopStatement {
     pub target_indexes: TargetIndexes,
 }
 
+/// How many seats of each group, as `(group, min, max)`, a truthful Bishop's
+/// named seats hold. A named Demon never fits, so it always makes it a lie.
+pub const BISHOP_COMPOSITION: [(Group, usize, usize); 4] = [
+    (Group::Villager, 1, 1),
+    (Group::Minion, 1, 1),
+    (Group::Outcast, 0, 1),
+    (Group::Demon, 0, 0),
+];
+
 impl fmt::Display for BishopStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = BISHOP_COMPOSITION
+            .iter()
+            .filter(|&&(_, _, max)| max > 0)
+            .map(|&(group, min, max)| {
+                let group = format!("{:?}", group).to_lowercase();
+                if min == max {
+                    format!("{} {}", max, group)
+                } else {
+                    format!("maybe {} {}", max, group)
+                }
+            })
+            .collect();
+        let (last, rest) = parts.split_last().expect("Bishop composition is empty");
+
         write!(
             f,
-            "Among {} there is 1 villager, 1 minion, and maybe 1 outcast",
+            "Among {} there is {}, and {}",
             self.target_indexes
                 .iter_ones()
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>()
-                .join(", ")
+                .join(", "),
+            rest.join(", "),
+            last
         )
     }
 }
@@ -1128,11 +1755,17 @@ impl fmt::Display for ConfessorStatement {
 pub struct DreamerStatement {
     pub target_index: usize,
     pub role: Role,
+    /// The seat can't be `role` if it's evil, rather than can only be it
+    pub negated: bool,
 }
 
 impl fmt::Display for DreamerStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} could be {}", self.target_index, self.role)
+        if self.negated {
+            write!(f, "{} could not be {}", self.target_index, self.role)
+        } else {
+            write!(f, "{} could be {}", self.target_index, self.role)
+        }
     }
 }
 
@@ -1286,7 +1919,10 @@ pub struct KnitterStatement {
 
 impl fmt::Display for KnitterStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "There are {} Evil pairs", self.adjacent_count)
+        match self.adjacent_count {
+            1 => write!(f, "There is 1 Evil pair"),
+            n => write!(f, "There are {} Evil pairs", n),
+        }
     }
 }
 
@@ -1313,10 +1949,57 @@ impl fmt::Display for MediumStatement {
     }
 }
 
+/// What the Oracle says one of the named seats is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleClaim {
+    Role(Role),
+    /// Any role of the group, e.g. "one of these is an Outcast"
+    Group(Group),
+}
+
+impl OracleClaim {
+    pub fn matches(self, role: Role) -> bool {
+        match self {
+            OracleClaim::Role(claimed) => role == claimed,
+            OracleClaim::Group(group) => role.group() == group,
+        }
+    }
+}
+
+impl FromStr for OracleClaim {
+    type Err = String;
+
+    /// Roles first, so "minion" is the Minion role, "group:minion" is any minion
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(group) = s.trim().to_lowercase().strip_prefix("group:") {
+            return group
+                .parse()
+                .map(OracleClaim::Group)
+                .map_err(|_| format!("'{}' is not a group", group));
+        }
+        match s.trim().to_lowercase().parse() {
+            Ok(role) => Ok(OracleClaim::Role(role)),
+            Err(_) => s
+                .parse()
+                .map(OracleClaim::Group)
+                .map_err(|_| format!("'{}' is neither a role nor a group", s.trim())),
+        }
+    }
+}
+
+impl fmt::Display for OracleClaim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OracleClaim::Role(role) => write!(f, "{}", role),
+            OracleClaim::Group(group) => write!(f, "{:?}", group),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OracleStatement {
     pub target_indexes: TargetIndexes,
-    pub role: Role,
+    pub claim: OracleClaim,
 }
 
 impl fmt::Display for OracleStatement {
@@ -1329,7 +2012,7 @@ impl fmt::Display for OracleStatement {
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>()
                 .join(", "),
-            self.role
+            self.claim
         )
     }
 }
@@ -1366,6 +2049,8 @@ impl fmt::Display for SlayerStatement {
     }
 }
 
+/// With two seats the evil one always comes first, in the typed `evil;corrupt`
+/// form, the in-game text and `Display` alike.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlagueDoctorStatement {
     pub corruption_index: usize,
@@ -1385,8 +2070,89 @@ impl fmt::Display for PlagueDoctorStatement {
     }
 }
 
+/// How the seats are arranged. Around a circle the first and last seats are
+/// neighbours, in a line they are the two ends and nothing wraps around.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology {
+    #[default]
+    Circle,
+    Line,
+}
+
+/// Seats `offset` away from `position` counter-clockwise and clockwise, `None`
+/// for a side that runs past the end of a line
+fn sides(
+    len: usize,
+    position: usize,
+    offset: usize,
+    topology: Topology,
+) -> (Option<usize>, Option<usize>) {
+    match topology {
+        Topology::Circle => (
+            Some((position + len - offset) % len),
+            Some((position + offset) % len),
+        ),
+        Topology::Line => (
+            position.checked_sub(offset),
+            Some(position + offset).filter(|&i| i < len),
+        ),
+    }
+}
+
+/// Seats `offset` away from `position` on either side. Each seat is listed
+/// once, so around a small circle where both sides land on the same seat, or
+/// back on `position` itself, there are fewer than two.
 pub fn neighbor_indexes(len: usize, position: usize, offset: usize) -> Vec<usize> {
-    vec![(position + len - offset) % len, (position + offset) % len]
+    neighbor_indexes_on(len, position, offset, Topology::Circle)
+}
+
+/// Same as `neighbor_indexes`, with the seats arranged as `topology`
+pub fn neighbor_indexes_on(
+    len: usize,
+    position: usize,
+    offset: usize,
+    topology: Topology,
+) -> Vec<usize> {
+    let (left, right) = sides(len, position, offset, topology);
+    let mut seats: Vec<usize> = left
+        .into_iter()
+        .chain(right)
+        .filter(|&i| i != position)
+        .collect();
+    seats.dedup();
+    seats
+}
+
+/// The seats right next to `position`, each once: two around a circle of three
+/// or more, one around a circle of two or at the end of a line
+pub fn adjacent_seats(len: usize, position: usize, topology: Topology) -> Vec<usize> {
+    neighbor_indexes_on(len, position, 1, topology)
+}
+
+/// Turns a seat as the game numbers it into an index, failing on #0 and on
+/// seats past the 16 a statement can name
+fn seat_from_natural(seat: usize, s: &str) -> Result<usize, StatementParseError> {
+    let capacity = TargetIndexes::default().len();
+    match seat.checked_sub(1) {
+        Some(i) if i < capacity => Ok(i),
+        _ => Err(StatementParseError::InvalidIndex(format!(
+            "Seat #{} in '{}' doesn't exist, seats are numbered from 1 to {}",
+            seat, s, capacity
+        ))),
+    }
+}
+
+/// `to_bitvec` for seats that may not fit, an error instead of a panic for
+/// seats past the 16 a statement can name
+pub(crate) fn try_to_bitvec(indices: Vec<usize>) -> Result<TargetIndexes, StatementParseError> {
+    let capacity = TargetIndexes::default().len();
+    match indices.iter().find(|&&i| i >= capacity) {
+        Some(i) => Err(StatementParseError::InvalidIndex(format!(
+            "Seat {} is past the {} a statement can name",
+            i, capacity
+        ))),
+        None => Ok(to_bitvec(indices)),
+    }
 }
 
 pub fn to_bitvec(indices: Vec<usize>) -> TargetIndexes {
@@ -1397,32 +2163,89 @@ pub fn to_bitvec(indices: Vec<usize>) -> TargetIndexes {
     bits
 }
 
+pub fn evil_positions(board: &[Role]) -> Vec<usize> {
+    board
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.alignment().is_evil())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+pub fn minion_positions(board: &[Role]) -> Vec<usize> {
+    board
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.group() == Group::Minion)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Seat of the first demon on the board, if any
+pub fn demon_position(board: &[Role]) -> Option<usize> {
+    board.iter().position(|r| r.group() == Group::Demon)
+}
+
 fn count_evil<'a>(roles: impl IntoIterator<Item = &'a Role>) -> usize {
     roles
         .into_iter()
-        .filter(|role| role.alignment() == Alignment::Evil)
+        .filter(|role| role.alignment().is_evil())
         .count()
 }
 
-fn count_neighbor_evil(true_roles: &[Role], position: usize, offset: usize) -> usize {
+fn count_neighbor_evil(
+    true_roles: &[Role],
+    position: usize,
+    offset: usize,
+    topology: Topology,
+) -> usize {
     count_evil(
-        neighbor_indexes(true_roles.len(), position, offset)
+        neighbor_indexes_on(true_roles.len(), position, offset, topology)
             .iter()
             .map(|&i| &true_roles[i]),
     )
 }
 
+/// Farthest any seat can be from another around a circle of `len` seats,
+/// on even boards that is the seat straight across
+pub fn max_ring_distance(len: usize) -> usize {
+    len / 2
+}
+
+/// Farthest any seat can be from another with the seats arranged as `topology`
+pub fn max_distance(len: usize, topology: Topology) -> usize {
+    match topology {
+        Topology::Circle => max_ring_distance(len),
+        Topology::Line => len.saturating_sub(1),
+    }
+}
+
 pub fn closest_evil_direction(true_roles: &[Role], position: usize) -> EnlightenedStatement {
+    closest_evil_direction_on(true_roles, position, Topology::Circle)
+}
+
+/// Same as `closest_evil_direction`, with the seats arranged as `topology`
+pub fn closest_evil_direction_on(
+    true_roles: &[Role],
+    position: usize,
+    topology: Topology,
+) -> EnlightenedStatement {
     let len = true_roles.len();
-    let max_offset = (len + 1) / 2;
+    let is_evil = |seat: Option<usize>| seat.is_some_and(|i| true_roles[i].alignment().is_evil());
 
-    for offset in 1..=max_offset {
-        let neighbors = neighbor_indexes(len, position, offset);
-        let left = true_roles[neighbors[0]];
-        let right = true_roles[neighbors[1]];
+    for offset in 1..=max_distance(len, topology) {
+        let (left, right) = sides(len, position, offset, topology);
+        let left_evil = is_evil(left);
+        let right_evil = is_evil(right);
 
-        let left_evil = left.alignment() == Alignment::Evil;
-        let right_evil = right.alignment() == Alignment::Evil;
+        // On even boards the seat straight across is reached both ways, so it
+        // is a single seat that is equally far in each direction
+        if left == right {
+            if left_evil {
+                return EnlightenedStatement::Equidistant;
+            }
+            continue;
+        }
 
         match (left_evil, right_evil) {
             (true, true) => return EnlightenedStatement::Equidistant,
@@ -1435,45 +2258,75 @@ pub fn closest_evil_direction(true_roles: &[Role], position: usize) -> Enlighten
     EnlightenedStatement::Equidistant
 }
 
-pub fn closest_evil_distance(true_roles: &[Role], position: usize) -> usize {
-    let max_index = (true_roles.len() + 1) / 2;
-    (1..=max_index)
-        .find(|&i| count_neighbor_evil(true_roles, position, i) > 0)
-        .unwrap_or(true_roles.len())
+/// Distance to the closest evil seat other than `position`, `None` if there is none
+pub fn closest_evil_distance(true_roles: &[Role], position: usize) -> Option<usize> {
+    closest_evil_distance_on(true_roles, position, Topology::Circle)
+}
+
+/// Same as `closest_evil_distance`, with the seats arranged as `topology`
+pub fn closest_evil_distance_on(
+    true_roles: &[Role],
+    position: usize,
+    topology: Topology,
+) -> Option<usize> {
+    (1..=max_distance(true_roles.len(), topology))
+        .find(|&i| count_neighbor_evil(true_roles, position, i, topology) > 0)
 }
 
 pub fn closest_corrupt_distance(corruptions: &[bool], position: usize) -> Option<usize> {
-    let max_distance = corruptions.len() / 2 + 1;
+    closest_corrupt_distance_on(corruptions, position, Topology::Circle)
+}
 
-    (1..=max_distance).find(|&distance| {
-        neighbor_indexes(corruptions.len(), position, distance)
+/// Same as `closest_corrupt_distance`, with the seats arranged as `topology`
+pub fn closest_corrupt_distance_on(
+    corruptions: &[bool],
+    position: usize,
+    topology: Topology,
+) -> Option<usize> {
+    (1..=max_distance(corruptions.len(), topology)).find(|&distance| {
+        neighbor_indexes_on(corruptions.len(), position, distance, topology)
             .iter()
             .any(|&i| corruptions[i])
     })
 }
 
+/// Pairs of neighbouring evil seats, around a circle the last and first seat count too
 pub fn count_evil_pairs(true_roles: &[Role]) -> usize {
-    true_roles
+    count_evil_pairs_on(true_roles, Topology::Circle)
+}
+
+/// Same as `count_evil_pairs`, with the seats arranged as `topology`
+pub fn count_evil_pairs_on(true_roles: &[Role], topology: Topology) -> usize {
+    let is_evil = |role: &Role| role.alignment().is_evil();
+    let linear = true_roles
         .windows(2)
-        .filter(|w| w[0].alignment() == Alignment::Evil && w[1].alignment() == Alignment::Evil)
-        .count()
+        .filter(|w| is_evil(&w[0]) && is_evil(&w[1]))
+        .count();
+
+    // With two seats the wraparound pair is the one already counted
+    let wraps = topology == Topology::Circle
+        && true_roles.len() > 2
+        && true_roles.first().is_some_and(is_evil)
+        && true_roles.last().is_some_and(is_evil);
+    linear + usize::from(wraps)
 }
 
-fn count_side_evils(true_roles: &[Role]) -> ArchitectStatement {
+/// Which side of the board has more evil seats, for an Architect at
+/// `position`. The right side is the seats clockwise from it and the left side
+/// the seats counter-clockwise, each holding half of the other seats. On even
+/// boards the seat straight across is on neither side, and neither is the
+/// Architect's own seat, so a disguised evil doesn't weigh in on its own side.
+fn count_side_evils(true_roles: &[Role], position: usize) -> ArchitectStatement {
     let len = true_roles.len();
-    let half = len / 2;
-
-    let second_half_start = if len % 2 == 0 { half } else { half + 1 };
+    let side = len.saturating_sub(1) / 2;
 
-    let right_evil_count = true_roles[..half]
-        .iter()
-        .filter(|r| r.alignment() == Alignment::Evil)
-        .count();
-
-    let left_evil_count = true_roles[second_half_start..len - 1]
-        .iter()
-        .filter(|r| r.alignment() == Alignment::Evil)
-        .count();
+    let count_evils = |offsets: std::ops::RangeInclusive<usize>| {
+        offsets
+            .filter(|&offset| true_roles[(position + offset) % len].alignment().is_evil())
+            .count()
+    };
+    let right_evil_count = count_evils(1..=side);
+    let left_evil_count = count_evils(len - side..=len - 1);
 
     if left_evil_count > right_evil_count {
         ArchitectStatement::Left
@@ -1484,12 +2337,56 @@ fn count_side_evils(true_roles: &[Role]) -> ArchitectStatement {
     }
 }
 
+/// Whether every seat the statement refers to exists on a board of `len` seats
+pub fn targets_in_range(statement: &RoleStatement, len: usize) -> bool {
+    let all_in_range = |target_indexes: &TargetIndexes| target_indexes.iter_ones().all(|i| i < len);
+    match statement {
+        RoleStatement::Bishop(BishopStatement { target_indexes })
+        | RoleStatement::Druid(DruidStatement { target_indexes, .. })
+        | RoleStatement::Empress(EmpressStatement { target_indexes })
+        | RoleStatement::FortuneTeller(FortuneTellerStatement { target_indexes, .. })
+        | RoleStatement::Jester(JesterStatement { target_indexes, .. })
+        | RoleStatement::Oracle(OracleStatement { target_indexes, .. }) => {
+            all_in_range(target_indexes)
+        }
+        RoleStatement::Dreamer(DreamerStatement { target_index, .. })
+        | RoleStatement::Gemcrafter(GemcrafterStatement { target_index })
+        | RoleStatement::Judge(JudgeStatement { target_index, .. })
+        | RoleStatement::Medium(MediumStatement { target_index, .. })
+        | RoleStatement::Slayer(SlayerStatement { target_index, .. }) => *target_index < len,
+        RoleStatement::PlagueDoctor(PlagueDoctorStatement {
+            corruption_index,
+            evil_index,
+        }) => *corruption_index < len && evil_index.is_none_or(|i| i < len),
+        _ => true,
+    }
+}
+
+/// Whether a Bishop, Empress or Gemcrafter statement names the seat at
+/// `position` that made it. Those roles are shown other seats, so a statement
+/// naming the speaker can't come up in game, whether it's truthful, corrupted
+/// or an evil seat in disguise. The Jester picks its seats and may pick itself,
+/// it's counted like any other seat then.
+pub fn names_speaker(statement: &RoleStatement, position: usize) -> bool {
+    match statement {
+        RoleStatement::Bishop(BishopStatement { target_indexes })
+        | RoleStatement::Empress(EmpressStatement { target_indexes }) => {
+            target_indexes.get(position).is_some_and(|bit| *bit)
+        }
+        RoleStatement::Gemcrafter(GemcrafterStatement { target_index }) => {
+            *target_index == position
+        }
+        _ => false,
+    }
+}
+
 /// Check if a card can produce a specific statement given:
 /// - `visible_role`: what role is shown (may be a disguise)
 /// - `is_lying`: if the character should lie
 /// - `true_roles`: the true roles of all the cards in play
 /// - `position`: the index of the speaking card
 /// - `statement`: the statement to check
+#[allow(clippy::too_many_arguments)]
 pub fn can_produce_statement(
     visible_role: Role,
     is_lying: bool,
@@ -1500,22 +2397,60 @@ pub fn can_produce_statement(
     position: usize,
     statement: &RoleStatement,
 ) -> bool {
+    can_produce_statement_on(
+        visible_role,
+        is_lying,
+        true_roles,
+        disguised_roles,
+        corruptions,
+        drunk_uncorruptions,
+        position,
+        statement,
+        Topology::Circle,
+    )
+}
+
+/// Same as `can_produce_statement`, with the seats arranged as `topology`
+#[allow(clippy::too_many_arguments)]
+pub fn can_produce_statement_on(
+    visible_role: Role,
+    is_lying: bool,
+    true_roles: &[Role],
+    disguised_roles: &[Role],
+    corruptions: &[bool],
+    drunk_uncorruptions: &[usize],
+    position: usize,
+    statement: &RoleStatement,
+    topology: Topology,
+) -> bool {
+    // A statement about a seat that doesn't exist can't be made, truthfully or not
+    if !targets_in_range(statement, true_roles.len()) {
+        return false;
+    }
+    // Neither can a Bishop, Empress or Gemcrafter one about the speaker itself
+    if names_speaker(statement, position) {
+        return false;
+    }
+
     if is_lying {
         match visible_role {
             Role::Alchemist => {
                 if let RoleStatement::Alchemist(AlchemistStatement { corrupt_count }) = statement {
-                    *corrupt_count != 0
+                    // Lying seats never cure, so this is the same as claiming any cures at all
+                    *corrupt_count != drunk_uncorruptions[position]
                 } else {
                     false
                 }
             }
-            Role::Architect => *statement != RoleStatement::Architect(count_side_evils(true_roles)),
+            Role::Architect => {
+                *statement != RoleStatement::Architect(count_side_evils(true_roles, position))
+            }
             Role::Bard => {
-                let closest_distance = closest_corrupt_distance(corruptions, position);
+                let closest_distance = closest_corrupt_distance_on(corruptions, position, topology);
                 if let RoleStatement::Bard(BardStatement { distance }) = statement {
                     if let Some(stmt_dist) = distance {
                         *stmt_dist != closest_distance.unwrap_or(*stmt_dist + 1)
-                            && *stmt_dist <= (true_roles.len() + 1) / 2
+                            && *stmt_dist <= max_distance(true_roles.len(), topology)
                     } else {
                         closest_distance.is_some()
                     }
@@ -1534,9 +2469,14 @@ pub fn can_produce_statement(
             }
             Role::Confessor => *statement == RoleStatement::Confessor(ConfessorStatement::IAmDizzy),
             Role::Dreamer => {
-                if let RoleStatement::Dreamer(DreamerStatement { target_index, role }) = statement {
+                if let RoleStatement::Dreamer(DreamerStatement {
+                    target_index,
+                    role,
+                    negated,
+                }) = statement
+                {
                     let found_role = true_roles[*target_index];
-                    found_role.alignment() != Alignment::Evil || found_role != *role
+                    !found_role.alignment().is_evil() || (found_role == *role) == *negated
                 } else {
                     false
                 }
@@ -1564,13 +2504,13 @@ pub fn can_produce_statement(
                 if let RoleStatement::Empress(EmpressStatement { target_indexes }) = statement {
                     target_indexes
                         .iter_ones()
-                        .all(|i| true_roles[i].alignment() != Alignment::Evil)
+                        .all(|i| !true_roles[i].alignment().is_evil())
                 } else {
                     false
                 }
             }
             Role::Enlightened => {
-                let true_response = closest_evil_direction(true_roles, position);
+                let true_response = closest_evil_direction_on(true_roles, position, topology);
                 if let RoleStatement::Enlightened(stmt) = statement {
                     stmt != &true_response
                 } else {
@@ -1585,8 +2525,8 @@ pub fn can_produce_statement(
                 {
                     let any_evil = target_indexes
                         .iter_ones()
-                        .any(|i| true_roles[i].alignment() == Alignment::Evil);
-                    return any_evil != *is_evil;
+                        .any(|i| true_roles[i].alignment().is_evil());
+                    any_evil != *is_evil
                 } else {
                     false
                 }
@@ -1594,15 +2534,16 @@ pub fn can_produce_statement(
             Role::Gemcrafter => {
                 if let RoleStatement::Gemcrafter(GemcrafterStatement { target_index }) = statement {
                     *target_index < true_roles.len()
-                        && true_roles[*target_index].alignment() == Alignment::Evil
+                        && true_roles[*target_index].alignment().is_evil()
                 } else {
                     false
                 }
             }
             Role::Hunter => {
-                let index = closest_evil_distance(true_roles, position);
+                let index = closest_evil_distance_on(true_roles, position, topology);
                 if let RoleStatement::Hunter(HunterStatement { distance }) = statement {
-                    *distance != index
+                    Some(*distance) != index
+                        && (1..=max_distance(true_roles.len(), topology)).contains(distance)
                 } else {
                     false
                 }
@@ -1633,7 +2574,7 @@ pub fn can_produce_statement(
             }
             Role::Knitter => {
                 if let RoleStatement::Knitter(KnitterStatement { adjacent_count }) = statement {
-                    let true_adjacent_count = count_evil_pairs(true_roles);
+                    let true_adjacent_count = count_evil_pairs_on(true_roles, topology);
 
                     *adjacent_count != true_adjacent_count
                 } else {
@@ -1641,14 +2582,11 @@ pub fn can_produce_statement(
                 }
             }
             Role::Lover => {
-                let neighbors = neighbor_indexes(true_roles.len(), position, 1);
-                let real_evil_count = neighbors
-                    .iter()
-                    .filter(|&&idx| true_roles[idx].alignment() == Alignment::Evil)
-                    .count();
-
+                let neighbors = adjacent_seats(true_roles.len(), position, topology);
+                let real_evil_count = count_evil(neighbors.iter().map(|&i| &true_roles[i]));
                 if let RoleStatement::Lover(LoverStatement { evil_count }) = statement {
-                    *evil_count != real_evil_count && *evil_count <= 2
+                    // A lie still has to be a count its neighbours could have
+                    *evil_count != real_evil_count && *evil_count <= neighbors.len()
                 } else {
                     false
                 }
@@ -1663,19 +2601,13 @@ pub fn can_produce_statement(
                     false
                 }
             }
-            Role::Oracle => {
-                if let RoleStatement::Oracle(OracleStatement {
-                    target_indexes,
-                    role: _,
-                }) = statement
-                {
-                    target_indexes
-                        .iter_ones()
-                        .all(|i| true_roles[i].alignment() != Alignment::Evil)
-                } else {
-                    false
-                }
-            }
+            // A lying Oracle names no evil seat, whether it claims a role or a group
+            Role::Oracle => match statement {
+                RoleStatement::Oracle(OracleStatement { target_indexes, .. }) => target_indexes
+                    .iter_ones()
+                    .all(|i| !true_roles[i].alignment().is_evil()),
+                _ => false,
+            },
             Role::Scout => {
                 if let RoleStatement::Scout(ScoutStatement {
                     role: role_option,
@@ -1684,15 +2616,16 @@ pub fn can_produce_statement(
                 {
                     let evil_count = true_roles
                         .iter()
-                        .filter(|r| r.alignment() == Alignment::Evil)
+                        .filter(|r| r.alignment().is_evil())
                         .count();
 
                     if let Some(role) = role_option {
                         evil_count == 1
                             || !true_roles.iter().enumerate().any(|(idx, r)| {
                                 r == role
-                                    && *distance == closest_evil_distance(true_roles, idx)
-                                    && true_roles[idx].alignment() == Alignment::Evil
+                                    && Some(*distance)
+                                        == closest_evil_distance_on(true_roles, idx, topology)
+                                    && true_roles[idx].alignment().is_evil()
                             })
                     } else {
                         evil_count != 1
@@ -1730,7 +2663,7 @@ pub fn can_produce_statement(
                     false
                 }
             }
-            Role::Bombardier | Role::Wretch | Role::Knight => {
+            Role::Bombardier | Role::Wretch | Role::Knight | Role::Poet => {
                 *statement == RoleStatement::NoStatement
             }
             other => panic!(
@@ -1747,9 +2680,11 @@ pub fn can_produce_statement(
                     false
                 }
             }
-            Role::Architect => *statement == RoleStatement::Architect(count_side_evils(true_roles)),
+            Role::Architect => {
+                *statement == RoleStatement::Architect(count_side_evils(true_roles, position))
+            }
             Role::Bard => {
-                let closest_distance = closest_corrupt_distance(corruptions, position);
+                let closest_distance = closest_corrupt_distance_on(corruptions, position, topology);
                 if let RoleStatement::Bard(BardStatement { distance }) = statement {
                     *distance == closest_distance
                 } else {
@@ -1758,24 +2693,13 @@ pub fn can_produce_statement(
             }
             Role::Bishop => {
                 if let RoleStatement::Bishop(BishopStatement { target_indexes }) = statement {
-                    let mut villager_count = 0;
-                    let mut minion_count = 0;
-                    let mut outcast_count = 0;
-                    let mut other_count = 0;
-
-                    for i in target_indexes.iter_ones() {
-                        match true_roles[i].group() {
-                            Group::Villager => villager_count += 1,
-                            Group::Minion => minion_count += 1,
-                            Group::Outcast => outcast_count += 1,
-                            _ => other_count += 1,
-                        }
-                    }
-
-                    villager_count == 1
-                        && minion_count == 1
-                        && outcast_count <= 1
-                        && other_count == 0
+                    BISHOP_COMPOSITION.iter().all(|&(group, min, max)| {
+                        let count = target_indexes
+                            .iter_ones()
+                            .filter(|&i| true_roles[i].group() == group)
+                            .count();
+                        (min..=max).contains(&count)
+                    })
                 } else {
                     false
                 }
@@ -1802,26 +2726,27 @@ pub fn can_produce_statement(
             }
             Role::Enlightened => {
                 *statement
-                    == RoleStatement::Enlightened(closest_evil_direction(true_roles, position))
+                    == RoleStatement::Enlightened(closest_evil_direction_on(
+                        true_roles, position, topology,
+                    ))
             }
             Role::Empress => {
                 if let RoleStatement::Empress(EmpressStatement { target_indexes }) = statement {
-                    let (evil_count, good_count) =
-                        target_indexes
-                            .iter_ones()
-                            .fold((0, 0), |(evil, good), i| match true_roles[i].alignment() {
-                                Alignment::Evil => (evil + 1, good),
-                                Alignment::Good => (evil, good + 1),
-                            });
-                    evil_count == 1 && good_count == 2
+                    // However many seats are named, exactly one of them is evil
+                    count_evil(target_indexes.iter_ones().map(|i| &true_roles[i])) == 1
                 } else {
                     false
                 }
             }
             Role::Dreamer => {
-                if let RoleStatement::Dreamer(DreamerStatement { target_index, role }) = statement {
+                if let RoleStatement::Dreamer(DreamerStatement {
+                    target_index,
+                    role,
+                    negated,
+                }) = statement
+                {
                     let found_role = true_roles[*target_index];
-                    found_role.alignment() != Alignment::Evil || found_role == *role
+                    !found_role.alignment().is_evil() || (found_role == *role) != *negated
                 } else {
                     false
                 }
@@ -1834,8 +2759,8 @@ pub fn can_produce_statement(
                 {
                     let any_evil = target_indexes
                         .iter_ones()
-                        .any(|i| true_roles[i].alignment() == Alignment::Evil);
-                    return any_evil == *is_evil;
+                        .any(|i| true_roles[i].alignment().is_evil());
+                    any_evil == *is_evil
                 } else {
                     false
                 }
@@ -1849,9 +2774,10 @@ pub fn can_produce_statement(
                 }
             }
             Role::Hunter => {
-                let index = closest_evil_distance(true_roles, position);
+                // Without any evil there's no distance to tell truthfully
+                let index = closest_evil_distance_on(true_roles, position, topology);
                 if let RoleStatement::Hunter(HunterStatement { distance }) = statement {
-                    *distance == index
+                    Some(*distance) == index
                 } else {
                     false
                 }
@@ -1873,6 +2799,8 @@ pub fn can_produce_statement(
                     is_lying: stmt_lying,
                 }) = statement
                 {
+                    // Whatever shows as a Confessor only ever admits to being
+                    // dizzy, which is never a lie, so the Judge sees it as truthful
                     *stmt_lying
                         == ((true_roles[*target_index].lying() || corruptions[*target_index])
                             && disguised_roles[*target_index] != Role::Confessor)
@@ -1882,7 +2810,7 @@ pub fn can_produce_statement(
             }
             Role::Knitter => {
                 if let RoleStatement::Knitter(KnitterStatement { adjacent_count }) = statement {
-                    let true_adjacent_count = count_evil_pairs(true_roles);
+                    let true_adjacent_count = count_evil_pairs_on(true_roles, topology);
 
                     *adjacent_count == true_adjacent_count
                 } else {
@@ -1890,7 +2818,8 @@ pub fn can_produce_statement(
                 }
             }
             Role::Lover => {
-                let evil_count = count_neighbor_evil(true_roles, position, 1);
+                let neighbors = adjacent_seats(true_roles.len(), position, topology);
+                let evil_count = count_evil(neighbors.iter().map(|&i| &true_roles[i]));
                 if let RoleStatement::Lover(LoverStatement { evil_count: c }) = statement {
                     *c == evil_count
                 } else {
@@ -1909,17 +2838,18 @@ pub fn can_produce_statement(
             Role::Oracle => {
                 if let RoleStatement::Oracle(OracleStatement {
                     target_indexes,
-                    role,
+                    claim,
                 }) = statement
                 {
-                    let mut targets = target_indexes.iter_ones();
-                    let first = targets.next().unwrap();
-                    let second = targets.next().unwrap();
-
-                    (true_roles[first].alignment() == Alignment::Good
-                        && true_roles[second] == *role)
-                        || (true_roles[second].alignment() == Alignment::Good
-                            && true_roles[first] == *role)
+                    // One named seat is the role or in the group, and a different one is good
+                    target_indexes.count_ones() >= 2
+                        && target_indexes.iter_ones().any(|role_idx| {
+                            claim.matches(true_roles[role_idx])
+                                && target_indexes.iter_ones().any(|good_idx| {
+                                    good_idx != role_idx
+                                        && true_roles[good_idx].alignment() == Alignment::Good
+                                })
+                        })
                 } else {
                     false
                 }
@@ -1932,15 +2862,16 @@ pub fn can_produce_statement(
                 {
                     let evil_count = true_roles
                         .iter()
-                        .filter(|r| r.alignment() == Alignment::Evil)
+                        .filter(|r| r.alignment().is_evil())
                         .count();
 
                     if let Some(role) = role_option {
                         evil_count != 1
                             && true_roles.iter().enumerate().any(|(idx, r)| {
                                 r == role
-                                    && *distance == closest_evil_distance(true_roles, idx)
-                                    && true_roles[idx].alignment() == Alignment::Evil
+                                    && Some(*distance)
+                                        == closest_evil_distance_on(true_roles, idx, topology)
+                                    && true_roles[idx].alignment().is_evil()
                             })
                     } else {
                         evil_count == 1
@@ -1955,6 +2886,8 @@ pub fn can_produce_statement(
                     alignment,
                 }) = statement
                 {
+                    // Any evil seat dies, minion or demon, whatever it shows. Corruption
+                    // doesn't change a seat's alignment, so corrupted good seats survive.
                     *target_index < true_roles.len()
                         && *alignment == true_roles[*target_index].alignment()
                 } else {
@@ -1971,15 +2904,13 @@ pub fn can_produce_statement(
 
                     match evil_index {
                         None => !is_corrupt,
-                        Some(evil_idx) => {
-                            is_corrupt && true_roles[*evil_idx].alignment() == Alignment::Evil
-                        }
+                        Some(evil_idx) => is_corrupt && true_roles[*evil_idx].alignment().is_evil(),
                     }
                 } else {
                     false
                 }
             }
-            Role::Wretch | Role::Bombardier | Role::Knight => {
+            Role::Wretch | Role::Bombardier | Role::Knight | Role::Poet => {
                 *statement == RoleStatement::NoStatement
             }
             other => panic!(
@@ -1989,3 +2920,76 @@ pub fn can_produce_statement(
         }
     }
 }
+
+/// Every statement `visible_role` could truthfully make from `position`.
+/// Only roles whose truthful statements follow directly from the board are
+/// covered, for anything else this returns an empty list.
+pub fn possible_statements(
+    visible_role: Role,
+    true_roles: &[Role],
+    disguised_roles: &[Role],
+    corruptions: &[bool],
+    position: usize,
+) -> Vec<RoleStatement> {
+    possible_statements_on(
+        visible_role,
+        true_roles,
+        disguised_roles,
+        corruptions,
+        position,
+        Topology::Circle,
+    )
+}
+
+/// Same as `possible_statements`, with the seats arranged as `topology`
+pub fn possible_statements_on(
+    visible_role: Role,
+    true_roles: &[Role],
+    disguised_roles: &[Role],
+    corruptions: &[bool],
+    position: usize,
+    topology: Topology,
+) -> Vec<RoleStatement> {
+    match visible_role {
+        Role::Architect => vec![count_side_evils(true_roles, position).into()],
+        Role::Bard => vec![BardStatement {
+            distance: closest_corrupt_distance_on(corruptions, position, topology),
+        }
+        .into()],
+        Role::Confessor => vec![ConfessorStatement::IAmGood.into()],
+        Role::Enlightened => {
+            vec![closest_evil_direction_on(true_roles, position, topology).into()]
+        }
+        Role::Hunter => closest_evil_distance_on(true_roles, position, topology)
+            .map(|distance| HunterStatement { distance }.into())
+            .into_iter()
+            .collect(),
+        Role::Judge => (0..true_roles.len())
+            .filter(|&i| i != position)
+            .map(|target_index| {
+                JudgeStatement {
+                    target_index,
+                    is_lying: (true_roles[target_index].lying() || corruptions[target_index])
+                        && disguised_roles[target_index] != Role::Confessor,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Knitter => vec![KnitterStatement {
+            adjacent_count: count_evil_pairs_on(true_roles, topology),
+        }
+        .into()],
+        Role::Lover => vec![LoverStatement {
+            evil_count: count_evil(
+                adjacent_seats(true_roles.len(), position, topology)
+                    .iter()
+                    .map(|&i| &true_roles[i]),
+            ),
+        }
+        .into()],
+        Role::Bombardier | Role::Knight | Role::Poet | Role::Wretch => {
+            vec![RoleStatement::NoStatement]
+        }
+        _ => Vec::new(),
+    }
+}
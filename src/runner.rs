@@ -1,84 +1,182 @@
-use crate::brute_force_solve;
+use crate::definite_alignments;
+use crate::group_by_demon;
+use crate::puzzle::Puzzle;
+use crate::render::{render_board, solutions_to_csv, Coloring};
 use crate::roles::*;
 use crate::validate_candidate;
+use crate::{
+    forced_liars, format_solution_detailed, solve_with_stats, summarize_positions, try_solve,
+    try_solve_detailed,
+};
 use arboard::Clipboard;
 use colored::*;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
 use std::str::FromStr;
-use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+/// Somewhere clipboard text can be read from
+pub trait ClipboardSource {
+    /// The current text, `None` if it couldn't be read right now
+    fn get_text(&mut self) -> Option<String>;
+}
+
+impl ClipboardSource for Clipboard {
+    fn get_text(&mut self) -> Option<String> {
+        // Some platforms report the clipboard as busy now and then
+        Clipboard::get_text(self)
+            .map_err(|e| log::warn!("Failed to read the clipboard: {}", e))
+            .ok()
+    }
+}
+
+/// Reads `clipboard` once, returning its text if it changed since
+/// `last_content`. A failed read keeps `last_content` as it was, so the same
+/// puzzle isn't solved again once the clipboard can be read again.
+pub fn poll_clipboard(
+    clipboard: &mut impl ClipboardSource,
+    last_content: &mut String,
+) -> Option<String> {
+    let current_content = clipboard.get_text()?;
+    if current_content == *last_content {
+        return None;
+    }
+    *last_content = current_content.clone();
+    Some(current_content)
+}
+
 pub fn run_clipboard_loop() {
-    let clipboard = Arc::new(Mutex::new(
-        Clipboard::new().expect("Failed to initialize clipboard"),
-    ));
+    let mut clipboard = Clipboard::new().expect("Failed to initialize clipboard");
+    run_clipboard_loop_with(&mut clipboard, Duration::from_millis(200), |puzzle| {
+        print_clipboard_solutions(puzzle);
+        ControlFlow::Continue(())
+    });
+}
 
+/// Polls `clipboard` every `interval` and hands every newly copied puzzle to
+/// `solve`, until it returns `ControlFlow::Break`. Copied text that isn't a
+/// puzzle is reported on stderr.
+pub fn run_clipboard_loop_with<C, F>(clipboard: &mut C, interval: Duration, mut solve: F)
+where
+    C: ClipboardSource,
+    F: FnMut(&Puzzle) -> ControlFlow<()>,
+{
     let mut last_content = String::new();
 
     loop {
-        let current_content = {
-            let mut cb = clipboard.lock().unwrap();
-            cb.get_text().unwrap_or_default()
-        };
-
-        if current_content != last_content {
-            last_content = current_content.clone();
-            parse_clipboard(&current_content);
+        if let Some(content) = poll_clipboard(clipboard, &mut last_content) {
+            if let Some(puzzle) = parse_clipboard(&content) {
+                if solve(&puzzle).is_break() {
+                    return;
+                }
+            }
         }
 
-        thread::sleep(Duration::from_millis(200));
+        thread::sleep(interval);
     }
 }
 
 pub fn run_from_clipboard() {
-    let clipboard = Arc::new(Mutex::new(
-        Clipboard::new().expect("Failed to initialize clipboard"),
-    ));
+    let mut clipboard = Clipboard::new().expect("Failed to initialize clipboard");
 
-    let current_content = {
-        let mut cb = clipboard.lock().unwrap();
-        cb.get_text().unwrap_or_default()
+    let Some(current_content) = ClipboardSource::get_text(&mut clipboard) else {
+        eprintln!("Error: Failed to read the clipboard");
+        std::process::exit(1);
     };
-    parse_clipboard(&current_content);
+    match parse_clipboard(&current_content) {
+        Some(puzzle) => print_clipboard_solutions(&puzzle),
+        None => std::process::exit(1),
+    }
 }
 
-fn parse_clipboard(content: &str) {
+fn parse_clipboard(content: &str) -> Option<Puzzle> {
+    match parse_clipboard_puzzle(content) {
+        Ok(puzzle) => Some(puzzle),
+        Err(errors) => {
+            for e in errors {
+                eprintln!("Error: {}", e);
+            }
+            eprintln!("\nErrors were encountered in input.");
+            None
+        }
+    }
+}
+
+fn print_clipboard_solutions(puzzle: &Puzzle) {
+    run_solver_and_print(
+        &mut io::stdout(),
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+        true,
+        SolutionListing::Auto,
+        false,
+        false,
+        false,
+    )
+    .expect("Failed to write output");
+}
+
+/// Parses the clipboard format: the deck, then the villager, outcast, minion and
+/// demon counts (optionally followed by the total seat count as a cross-check),
+/// then one `index|visible|confirmed|statement` line per known seat with 1-based
+/// indexes. Returns every problem found rather than stopping at the first.
+pub fn parse_clipboard_puzzle(content: &str) -> Result<Puzzle, Vec<String>> {
     let lines: Vec<&str> = content.lines().collect();
     if lines.len() < 2 {
-        eprintln!("Clipboard content too short - expected at least 2 lines (deck and counts)");
-        return;
+        return Err(vec![
+            "Clipboard content too short - expected at least 2 lines (deck and counts)".to_string(),
+        ]);
     }
 
-    let deck = match parse_roles(lines[0]) {
-        Ok(deck) => deck,
-        Err(e) => {
-            eprintln!("Failed to parse deck '{}': {}", lines[0], e);
-            return;
-        }
-    };
+    let deck = parse_roles(lines[0])
+        .map_err(|e| vec![format!("Failed to parse deck '{}': {}", lines[0], e)])?;
 
     let count_parts: Vec<&str> = lines[1].split_whitespace().collect();
-    if count_parts.len() != 4 {
-        eprintln!(
-            "Expected 4 counts on the second line (villagers outcasts minions demons), found {}: '{}'",
+    if count_parts.len() != 4 && count_parts.len() != 5 {
+        return Err(vec![format!(
+            "Expected 4 counts on the second line (villagers outcasts minions demons), optionally followed by the seat count, found {}: '{}'",
             count_parts.len(),
             lines[1]
-        );
-        return;
+        )]);
     }
 
-    let villagers = parse_count(count_parts[0], "villagers", 1);
-    let outcasts = parse_count(count_parts[1], "outcasts", 1);
-    let minions = parse_count(count_parts[2], "minions", 1);
-    let demons = parse_count(count_parts[3], "demons", 1);
+    let mut errors = Vec::new();
+    let mut parse_count = |s: &str, name: &str| {
+        s.parse::<usize>().unwrap_or_else(|_| {
+            errors.push(format!(
+                "Invalid {} count on line 2: '{}' is not a valid number",
+                name, s
+            ));
+            0
+        })
+    };
+    let villagers = parse_count(count_parts[0], "villagers");
+    let outcasts = parse_count(count_parts[1], "outcasts");
+    let minions = parse_count(count_parts[2], "minions");
+    let demons = parse_count(count_parts[3], "demons");
+    let explicit_seats = count_parts.get(4).map(|s| parse_count(s, "seat"));
     let num_seats = villagers + outcasts + minions + demons;
 
+    if let Some(seats) = explicit_seats {
+        if seats != num_seats {
+            errors.push(format!(
+                "Counts add up to {} seats ({} villagers, {} outcasts, {} minions, {} demons) but the seat count is {}",
+                num_seats, villagers, outcasts, minions, demons, seats
+            ));
+        }
+    }
+
     let mut visible = vec![None; num_seats];
     let mut confirmed: Vec<Option<Role>> = vec![None; num_seats];
     let mut observed = vec![RoleStatement::NoStatement; num_seats];
 
-    let mut has_errors = false;
-
     for line in &lines[2..] {
         let parts: Vec<&str> = line.split('|').collect();
         if parts.len() < 2 {
@@ -87,22 +185,21 @@ fn parse_clipboard(content: &str) {
 
         let index = match parts[0].trim().parse::<usize>() {
             Ok(idx) if idx > 0 && idx <= num_seats => idx - 1,
+            // A seat past the counts usually means a count is off
             Ok(idx) => {
-                eprintln!(
-                    "Error: Index {} out of bounds (must be 1-{}) in line: {}",
-                    idx, num_seats, line
-                );
-                has_errors = true;
+                errors.push(format!(
+                    "Index {} out of bounds (must be 1-{}, the counts add up to {} seats: {} villagers, {} outcasts, {} minions, {} demons) in line: {}",
+                    idx, num_seats, num_seats, villagers, outcasts, minions, demons, line
+                ));
                 continue;
             }
             Err(e) => {
-                eprintln!(
-                    "Error: Invalid index '{}' in line: {} ({})",
+                errors.push(format!(
+                    "Invalid index '{}' in line: {} ({})",
                     parts[0].trim(),
                     line,
                     e
-                );
-                has_errors = true;
+                ));
                 continue;
             }
         };
@@ -110,11 +207,10 @@ fn parse_clipboard(content: &str) {
         let vis_role = match parse_role(parts[1]) {
             Ok(role) => role,
             Err(e) => {
-                eprintln!(
-                    "Error: Invalid visible role '{}' in line: {} ({})",
+                errors.push(format!(
+                    "Invalid visible role '{}' in line: {} ({})",
                     parts[1], line, e
-                );
-                has_errors = true;
+                ));
                 None
             }
         };
@@ -126,51 +222,50 @@ fn parse_clipboard(content: &str) {
                     confirmed[index] = role;
                 }
                 Err(e) => {
-                    eprintln!(
-                        "Error: Invalid confirmed role '{}' in line: {} ({})",
+                    errors.push(format!(
+                        "Invalid confirmed role '{}' in line: {} ({})",
                         parts[2], line, e
-                    );
-                    has_errors = true;
+                    ));
                 }
             }
         }
 
-        if parts.len() >= 4 && parts[3] != "" {
+        if parts.len() >= 4 && !parts[3].is_empty() {
             if let Some(role) = vis_role {
                 match role.parse_natural_statement(parts[3]) {
                     Ok(statement) => {
                         observed[index] = statement;
                     }
                     Err(e) => {
-                        eprintln!(
-                            "Error: Invalid statement '{}' for {:?} in line: {} ({})",
+                        errors.push(format!(
+                            "Invalid statement '{}' for {:?} in line: {} ({})",
                             parts[3], role, line, e
-                        );
-                        has_errors = true;
+                        ));
                     }
                 }
             }
         }
     }
 
-    if has_errors {
-        eprintln!("\nErrors were encountered in input. Exiting.");
-        std::process::exit(1);
+    if !errors.is_empty() {
+        return Err(errors);
     }
 
-    run_solver_and_print(
-        &deck, &visible, &confirmed, &observed, villagers, outcasts, minions, demons, true,
-    );
-}
-
-fn parse_count(s: &str, name: &str, line_num: usize) -> usize {
-    s.parse().unwrap_or_else(|_| {
-        eprintln!(
-            "Invalid {} count on line {}: '{}' is not a valid number",
-            name, line_num, s
-        );
-        0
-    })
+    let puzzle = Puzzle {
+        deck,
+        visible,
+        confirmed,
+        observed,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    };
+    let errors = puzzle.roles_not_in_deck();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(puzzle)
 }
 
 fn parse_role(s: &str) -> Result<Option<Role>, String> {
@@ -185,19 +280,141 @@ fn parse_role(s: &str) -> Result<Option<Role>, String> {
     }
 }
 
+/// What a command line run concluded, see `exit_code` for the process exit codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// Exactly one solution, or the `--validate` candidate is valid
+    Unique,
+    /// The arguments couldn't be parsed
+    InvalidInput,
+    /// No solutions, or the `--validate` candidate is invalid
+    NoSolutions,
+    /// More than one solution
+    MultipleSolutions,
+}
+
+impl RunOutcome {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            RunOutcome::Unique => 0,
+            RunOutcome::InvalidInput => 1,
+            RunOutcome::NoSolutions => 2,
+            RunOutcome::MultipleSolutions => 3,
+        }
+    }
+}
+
 pub fn run_args(args: Vec<String>) {
+    let outcome = run_args_to(args, &mut io::stdout()).expect("Failed to write output");
+    std::process::exit(outcome.exit_code());
+}
+
+/// Same as `run_args`, but writes the results to `out` instead of stdout and
+/// returns the outcome instead of exiting
+pub fn run_args_to(args: Vec<String>, out: &mut impl Write) -> io::Result<RunOutcome> {
+    let mut args = args;
+
+    let all = if let Some(all_pos) = args.iter().position(|x| x == "--all") {
+        args.remove(all_pos);
+        true
+    } else {
+        false
+    };
+    let limit = if let Some(limit_pos) = args.iter().position(|x| x == "--limit") {
+        let limit = match args.get(limit_pos + 1).map(|l| l.parse::<usize>()) {
+            Some(Ok(limit)) => limit,
+            _ => {
+                writeln!(
+                    out,
+                    "Error: --limit requires a number of solutions to print"
+                )?;
+                return Ok(RunOutcome::InvalidInput);
+            }
+        };
+        args.drain(limit_pos..=limit_pos + 1);
+        Some(limit)
+    } else {
+        None
+    };
+    let listing = match (all, limit) {
+        (true, Some(_)) => {
+            writeln!(out, "Error: --all and --limit can't be used together")?;
+            return Ok(RunOutcome::InvalidInput);
+        }
+        (true, None) => SolutionListing::All,
+        (false, Some(limit)) => SolutionListing::Limit(limit),
+        (false, None) => SolutionListing::Auto,
+    };
+
+    // Only the solution count and the per-position summary
+    let quiet = if let Some(quiet_pos) = args.iter().position(|x| x == "--quiet") {
+        args.remove(quiet_pos);
+        true
+    } else {
+        false
+    };
+
+    // Solutions also tell which seats are disguised or corrupted
+    let explain = if let Some(explain_pos) = args.iter().position(|x| x == "--explain") {
+        args.remove(explain_pos);
+        true
+    } else {
+        false
+    };
+
+    // How long solving took and how much of the search was cut off early
+    let time = if let Some(time_pos) = args.iter().position(|x| x == "--time") {
+        args.remove(time_pos);
+        true
+    } else {
+        false
+    };
+
+    // Only the solutions, as CSV for spreadsheets
+    let csv = if let Some(csv_pos) = args.iter().position(|x| x == "--csv") {
+        args.remove(csv_pos);
+        true
+    } else {
+        false
+    };
+
+    // Statements number their seats from 1, like the output does
+    let one_based = if let Some(one_based_pos) = args.iter().position(|x| x == "--one-based") {
+        args.remove(one_based_pos);
+        true
+    } else {
+        false
+    };
+
+    let seats = if let Some(seats_pos) = args.iter().position(|x| x == "--seats") {
+        let seats = match args.get(seats_pos + 1).map(|l| l.parse::<usize>()) {
+            Some(Ok(seats)) => seats,
+            _ => {
+                writeln!(out, "Error: --seats requires the number of seats")?;
+                return Ok(RunOutcome::InvalidInput);
+            }
+        };
+        args.drain(seats_pos..=seats_pos + 1);
+        Some(seats)
+    } else {
+        None
+    };
+
     let (validate_mode, candidate, filtered_args) =
         if let Some(validate_pos) = args.iter().position(|x| x == "--validate") {
             if validate_pos + 1 >= args.len() {
-                eprintln!("Error: --validate requires a candidate argument");
-                std::process::exit(1);
+                writeln!(out, "Error: --validate requires a candidate argument")?;
+                return Ok(RunOutcome::InvalidInput);
             }
 
             let candidate_str = &args[validate_pos + 1];
-            let candidate = parse_roles(candidate_str).unwrap_or_else(|e| {
-                eprintln!("Failed to parse candidate roles: {}", e);
-                std::process::exit(1);
-            });
+            let candidate = match parse_roles(candidate_str) {
+                Ok(candidate) => candidate,
+                Err(e) => {
+                    writeln!(out, "Failed to parse candidate roles: {}", e)?;
+                    return Ok(RunOutcome::InvalidInput);
+                }
+            };
 
             let mut filtered_args = args.clone();
             filtered_args.drain(validate_pos..=validate_pos + 1);
@@ -207,14 +424,36 @@ pub fn run_args(args: Vec<String>) {
             (false, None, args)
         };
 
-    let (deck, visible, confirmed, observed, villagers, outcasts, minions, demons) =
-        match parse_input(&filtered_args) {
-            Ok(parsed) => parsed,
-            Err(e) => {
-                println!("{}", e);
-                return;
-            }
-        };
+    let Puzzle {
+        deck,
+        visible,
+        confirmed,
+        observed,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    } = match parse_cli_puzzle_indexed(&filtered_args, one_based) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            writeln!(out, "{}", e)?;
+            return Ok(RunOutcome::InvalidInput);
+        }
+    };
+
+    if let Some(seats) = seats {
+        let counted = villagers + outcasts + minions + demons;
+        if counted != seats || visible.len() != seats {
+            writeln!(
+                out,
+                "Expected {} seats, but the counts add up to {} and {} seats were given",
+                seats,
+                counted,
+                visible.len()
+            )?;
+            return Ok(RunOutcome::InvalidInput);
+        }
+    }
 
     if validate_mode {
         match candidate {
@@ -223,53 +462,76 @@ pub fn run_args(args: Vec<String>) {
                     &candidate, &deck, &visible, &confirmed, &observed, villagers, outcasts,
                     minions, demons,
                 ) {
-                    Ok(_) => println!("{}", "Candidate is valid!".green()),
+                    Ok(_) => {
+                        writeln!(out, "{}", "Candidate is valid!".green())?;
+                        Ok(RunOutcome::Unique)
+                    }
                     Err(reasons) => {
-                        println!("{}", "Candidate is invalid:".red());
+                        writeln!(out, "{}", "Candidate is invalid:".red())?;
                         for reason in reasons {
-                            println!("- {}", reason);
+                            writeln!(out, "- {}", reason)?;
                         }
+                        write_forced_liars(out, &observed, minions, demons)?;
+                        Ok(RunOutcome::NoSolutions)
                     }
                 }
             }
             None => {
-                eprintln!("Error: No candidate provided for validation");
-                std::process::exit(1);
+                writeln!(out, "Error: No candidate provided for validation")?;
+                Ok(RunOutcome::InvalidInput)
+            }
+        }
+    } else if csv {
+        match try_solve(
+            &deck, &visible, &confirmed, &observed, villagers, outcasts, minions, demons,
+        ) {
+            Ok(sols) => {
+                write!(out, "{}", solutions_to_csv(visible.len(), &sols))?;
+                Ok(match sols.len() {
+                    0 => RunOutcome::NoSolutions,
+                    1 => RunOutcome::Unique,
+                    _ => RunOutcome::MultipleSolutions,
+                })
+            }
+            Err(e) => {
+                writeln!(out, "Invalid puzzle: {}", e)?;
+                Ok(RunOutcome::InvalidInput)
             }
         }
     } else {
-        run_solver_and_print(
-            &deck, &visible, &confirmed, &observed, villagers, outcasts, minions, demons, false,
-        );
+        let solution_count = run_solver_and_print(
+            out, &deck, &visible, &confirmed, &observed, villagers, outcasts, minions, demons,
+            false, listing, quiet, explain, time,
+        )?;
+        Ok(match solution_count {
+            None => RunOutcome::InvalidInput,
+            Some(0) => RunOutcome::NoSolutions,
+            Some(1) => RunOutcome::Unique,
+            Some(_) => RunOutcome::MultipleSolutions,
+        })
     }
 }
 
-fn parse_input(
-    args: &[String],
-) -> Result<
-    (
-        Vec<Role>,
-        Vec<Option<Role>>,
-        Vec<Option<Role>>,
-        Vec<RoleStatement>,
-        usize,
-        usize,
-        usize,
-        usize,
-    ),
-    String,
-> {
+/// Parses the command line format (program name first, then the deck, the four
+/// counts and one `visible:confirmed:statement` argument per seat). Flags like
+/// `--validate` have to be taken out beforehand.
+pub fn parse_cli_puzzle(args: &[String]) -> Result<Puzzle, String> {
+    parse_cli_puzzle_indexed(args, false)
+}
+
+/// `parse_cli_puzzle`, with statements numbering seats from 1 if `one_based`
+fn parse_cli_puzzle_indexed(args: &[String], one_based: bool) -> Result<Puzzle, String> {
     if args.len() < 6 {
         return Err(format!(
             "Usage: {} <deck> <villagers> <outcasts> <minions> <demons> [visible:confirmed:statement...]\nGot {} arguments",
-            args[0],
-            args.len() - 1
+            args.first().map_or("demon_deduce", String::as_str),
+            args.len().saturating_sub(1)
         ));
     }
 
     let deck = parse_roles(&args[1]).map_err(|e| {
         format!(
-            "Failed to parse deck '{}': {}\nExpected comma-separated roles like 'bard,confessor,empress'",
+            "Failed to parse deck '{}': {}\nExpected comma-separated roles like 'bard,confessor,empress' or 'confessor x3,minion'",
             args[1], e
         )
     })?;
@@ -282,19 +544,19 @@ fn parse_input(
     })?;
     let outcasts = args[3].parse().map_err(|_| {
         format!(
-            "Invalid minions count '{}': must be a positive integer",
+            "Invalid outcasts count '{}': must be a positive integer",
             args[3]
         )
     })?;
     let minions = args[4].parse().map_err(|_| {
         format!(
-            "Invalid demons count '{}': must be a positive integer",
+            "Invalid minions count '{}': must be a positive integer",
             args[4]
         )
     })?;
     let demons = args[5].parse().map_err(|_| {
         format!(
-            "Invalid outcasts count '{}': must be a positive integer",
+            "Invalid demons count '{}': must be a positive integer",
             args[5]
         )
     })?;
@@ -346,7 +608,12 @@ fn parse_input(
                         position, card_arg
                     )
                 })?;
-                role.parse_statement(parts[2]).map_err(|e| {
+                if one_based {
+                    role.parse_statement_one_based(parts[2])
+                } else {
+                    role.parse_statement(parts[2])
+                }
+                .map_err(|e| {
                     format!(
                         "Invalid statement '{}' for role {:?} in argument {} ('{}'): {}",
                         parts[2], role, position, card_arg, e
@@ -356,12 +623,35 @@ fn parse_input(
         );
     }
 
-    Ok((
-        deck, visible, confirmed, observed, villagers, outcasts, minions, demons,
-    ))
+    let puzzle = Puzzle {
+        deck,
+        visible,
+        confirmed,
+        observed,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    };
+    let errors = puzzle.roles_not_in_deck();
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok(puzzle)
 }
 
+/// How many of the individual solutions to list before the per-position summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SolutionListing {
+    /// List them only when there are fewer than 25
+    Auto,
+    All,
+    Limit(usize),
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_solver_and_print(
+    out: &mut impl Write,
     deck: &[Role],
     visible: &[Option<Role>],
     confirmed: &[Option<Role>],
@@ -371,95 +661,204 @@ fn run_solver_and_print(
     minions: usize,
     demons: usize,
     print_statements: bool,
-) {
-    if print_statements {
-        println!("Deck: {:?}", deck);
-        println!(
+    listing: SolutionListing,
+    quiet: bool,
+    explain: bool,
+    time: bool,
+) -> io::Result<Option<usize>> {
+    if print_statements && !quiet {
+        writeln!(out, "Deck: {:?}", deck)?;
+        writeln!(
+            out,
             "Villagers: {}, Outcasts: {}, Minions: {}, Demons: {}",
             villagers, outcasts, minions, demons,
-        );
+        )?;
 
-        for i in 0..visible.len() {
-            let vis = match visible[i] {
-                Some(role) => {
-                    let confirmed_part = match confirmed[i] {
-                        Some(c_role) if c_role != role => format!(" ({:?})", c_role),
-                        _ => String::new(),
-                    };
-                    format!("{:?}{}", role, confirmed_part)
-                }
-                None => "Unrevealed".to_string(),
-            };
+        write!(
+            out,
+            "{}",
+            render_board(&[], visible, confirmed, observed, Coloring::None)
+        )?;
+    }
 
-            println!("Player {}: {} - {}", i, vis, observed[i]);
+    // Malformed input is reported rather than panicking or coming up empty
+    let solved = if time {
+        let puzzle = Puzzle {
+            deck: deck.to_vec(),
+            visible: visible.to_vec(),
+            confirmed: confirmed.to_vec(),
+            observed: observed.to_vec(),
+            villagers,
+            outcasts,
+            minions,
+            demons,
+        };
+        solve_with_stats(&puzzle).map(|(detailed, stats)| (detailed, Some(stats)))
+    } else {
+        try_solve_detailed(
+            deck, visible, confirmed, observed, villagers, outcasts, minions, demons,
+        )
+        .map(|detailed| (detailed, None))
+    };
+    let detailed = match solved {
+        Ok((detailed, stats)) => {
+            if let Some(stats) = stats {
+                writeln!(out, "{}", stats)?;
+            }
+            detailed
         }
-    }
+        Err(e) => {
+            writeln!(out, "Invalid puzzle: {}", e)?;
+            return Ok(None);
+        }
+    };
 
-    let sols = brute_force_solve(
-        deck, visible, confirmed, observed, villagers, outcasts, minions, demons, false,
-    );
+    let sols: Vec<Vec<Role>> = detailed
+        .iter()
+        .map(|solution| solution.true_roles.clone())
+        .collect();
 
-    if sols.len() == 0 {
-        println!("No solutions found.");
-        _ = brute_force_solve(
-            deck, visible, confirmed, observed, villagers, outcasts, minions, demons, true,
-        );
-        return;
+    if sols.is_empty() {
+        writeln!(out, "No solutions found.")?;
+        if !quiet {
+            write_forced_liars(out, observed, minions, demons)?;
+        }
+        return Ok(Some(0));
     }
 
-    println!("Found {} solution(s)", sols.len());
+    writeln!(out, "Found {} solution(s)", sols.len())?;
+    if !quiet {
+        write_forced_liars(out, observed, minions, demons)?;
+    }
 
-    if sols.len() < 25 {
-        for s in &sols {
-            let line: Vec<String> = s.iter().map(|role| color_by_alignment(*role)).collect();
-            println!("{}", line.join(", "));
+    let listed = match listing {
+        _ if quiet => 0,
+        SolutionListing::Auto if sols.len() < 25 => sols.len(),
+        SolutionListing::Auto => 0,
+        SolutionListing::All => sols.len(),
+        SolutionListing::Limit(limit) => limit.min(sols.len()),
+    };
+    for solution in &detailed[..listed] {
+        if explain {
+            writeln!(out, "{}", format_solution_detailed(solution))?;
+            continue;
         }
+        let line: Vec<String> = solution
+            .true_roles
+            .iter()
+            .map(|role| Coloring::Alignment.paint(*role))
+            .collect();
+        writeln!(out, "{}", line.join(", "))?;
+    }
+
+    writeln!(out, "\nPossible roles per position:")?;
+    write_possible_roles(out, &sols)?;
+
+    let demons = group_by_demon(&sols);
+    if !demons.is_empty() && !quiet {
+        let places: Vec<String> = demons
+            .iter()
+            .map(|&(seat, role, count)| {
+                format!(
+                    "seat {} ({}) in {} board{}",
+                    seat + 1,
+                    role.display_name(),
+                    count,
+                    if count == 1 { "" } else { "s" }
+                )
+            })
+            .collect();
+        writeln!(out, "\nDemon could be {}", places.join(", "))?;
     }
 
-    println!("\nPossible roles per position:");
-    for (i, _) in sols[0].iter().enumerate() {
-        // Collect all roles that appear at this position across all solutions
-        let mut possible_roles: Vec<Role> = sols.iter().map(|sol| sol[i]).collect();
-        possible_roles.sort();
-        possible_roles.dedup();
-        let line: Vec<String> = possible_roles
+    if !quiet {
+        let definite: Vec<(usize, Alignment)> = definite_alignments(&sols)
             .into_iter()
-            .map(|role| color_by_group(role))
+            .enumerate()
+            .filter_map(|(seat, alignment)| alignment.map(|alignment| (seat, alignment)))
             .collect();
-        println!("{}: {}", i + 1, line.join(", "));
+        if !definite.is_empty() {
+            writeln!(out)?;
+        }
+        for (seat, alignment) in definite {
+            writeln!(out, "Seat {}: definitely {:?}", seat + 1, alignment)?;
+        }
     }
+
+    Ok(Some(sols.len()))
 }
 
-fn color_by_alignment(role: Role) -> String {
-    match role.alignment() {
-        Alignment::Good => format!("{}", format!("{:?}", role).green()),
-        Alignment::Evil => format!("{}", format!("{:?}", role).red()),
+/// A line for each seat whose statement can't be true with the evil counts
+fn write_forced_liars(
+    out: &mut impl Write,
+    observed: &[RoleStatement],
+    minions: usize,
+    demons: usize,
+) -> io::Result<()> {
+    for seat in forced_liars(observed, minions, demons) {
+        writeln!(
+            out,
+            "Seat {} can't be telling the truth with {} evil seats in play",
+            seat + 1,
+            minions + demons
+        )?;
     }
+    Ok(())
 }
 
-fn color_by_group(role: Role) -> String {
-    match role.group() {
-        Group::Villager => format!("{}", format!("{:?}", role).green()),
-        Group::Outcast => format!("{}", format!("{:?}", role).yellow()),
-        Group::Minion => format!("{}", format!("{:?}", role).red()),
-        Group::Demon => format!("{}", format!("{:?}", role).bright_red()),
+/// One `seat: roles` line per seat with every role it has in some solution
+pub(crate) fn write_possible_roles(out: &mut impl Write, sols: &[Vec<Role>]) -> io::Result<()> {
+    for summary in summarize_positions(sols) {
+        let line: Vec<String> = summary
+            .possible
+            .into_iter()
+            .map(|role| Coloring::Group.paint(role))
+            .collect();
+        writeln!(out, "{}: {}", summary.index + 1, line.join(", "))?;
     }
+    Ok(())
 }
 
+/// Parses a comma-separated deck. An entry can be `role xN` or `N*role` for N
+/// copies of the role.
 fn parse_roles(s: &str) -> Result<Vec<Role>, String> {
-    s.to_lowercase()
-        .split(',')
-        .enumerate()
-        .map(|(i, r)| {
-            let trimmed = r.trim();
-            Role::from_str(trimmed).map_err(|e| {
+    let mut roles = Vec::new();
+    for (i, r) in s.to_lowercase().split(',').enumerate() {
+        let trimmed = r.trim();
+        let (role, copies) = split_multiplier(trimmed)
+            .and_then(|(name, copies)| {
+                Ok((Role::from_str(name).map_err(|e| e.to_string())?, copies))
+            })
+            .map_err(|e| {
                 format!(
                     "Failed to parse role '{}' at position {}: {}",
                     trimmed,
                     i + 1,
                     e
                 )
-            })
-        })
-        .collect::<Result<Vec<_>, _>>()
+            })?;
+        roles.extend(std::iter::repeat_n(role, copies));
+    }
+    Ok(roles)
+}
+
+/// Splits `role xN` or `N*role` into the role name and N, plain roles count once
+fn split_multiplier(s: &str) -> Result<(&str, usize), String> {
+    let (name, count) = if let Some((count, name)) = s.split_once('*') {
+        (name.trim(), count.trim())
+    } else if let Some((name, count)) = s
+        .rsplit_once(char::is_whitespace)
+        .and_then(|(name, count)| Some((name, count.strip_prefix('x')?)))
+    {
+        (name.trim(), count)
+    } else {
+        return Ok((s, 1));
+    };
+    match count.parse() {
+        Ok(0) | Err(_) => Err(format!(
+            "Invalid multiplier '{}', expected a positive number like 'confessor x3' or '3*confessor'",
+            count
+        )),
+        Ok(copies) => Ok((name, copies)),
+    }
 }
@@ -0,0 +1,547 @@
+use crate::puzzle::Puzzle;
+use crate::roles::*;
+
+/// Bumped whenever the byte layout changes, so old tokens fail loudly
+const FORMAT_VERSION: u8 = 1;
+
+/// Every role by its number in a token, which is its position here. New roles
+/// go at the end and nothing here moves, so reordering `Role` leaves old tokens
+/// meaning what they did. Starts out in the order `Role` had when tokens were
+/// added, which tokens from then on were written in.
+const ROLE_IDS: &[Role] = &[
+    Role::Alchemist,
+    Role::Architect,
+    Role::Baker,
+    Role::Bard,
+    Role::Bishop,
+    Role::Confessor,
+    Role::Dreamer,
+    Role::Druid,
+    Role::Empress,
+    Role::Enlightened,
+    Role::FortuneTeller,
+    Role::Gemcrafter,
+    Role::Hunter,
+    Role::Jester,
+    Role::Judge,
+    Role::Knight,
+    Role::Knitter,
+    Role::Lover,
+    Role::Medium,
+    Role::Oracle,
+    Role::Poet,
+    Role::Scout,
+    Role::Slayer,
+    Role::Witness,
+    Role::Bombardier,
+    Role::DoppelGanger,
+    Role::Drunk,
+    Role::PlagueDoctor,
+    Role::Wretch,
+    Role::Counsellor,
+    Role::Minion,
+    Role::Poisoner,
+    Role::Puppet,
+    Role::Puppeteer,
+    Role::Shaman,
+    Role::TwinMinion,
+    Role::Witch,
+    Role::Baa,
+    Role::Lilis,
+    Role::Pooka,
+];
+
+/// URL-safe base64 alphabet, the token is written without padding
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Packs a puzzle into a short token that's safe to paste into chats and URLs.
+/// Roles are stored by their number in `ROLE_IDS`, so a token decodes the same
+/// in later builds.
+pub fn encode_puzzle(puzzle: &Puzzle) -> String {
+    let mut bytes = vec![FORMAT_VERSION];
+
+    write_number(&mut bytes, puzzle.deck.len());
+    for &role in &puzzle.deck {
+        write_role(&mut bytes, role);
+    }
+    for count in [
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    ] {
+        write_number(&mut bytes, count);
+    }
+
+    write_number(&mut bytes, puzzle.visible.len());
+    for i in 0..puzzle.visible.len() {
+        write_optional_role(&mut bytes, puzzle.visible[i]);
+        write_optional_role(&mut bytes, puzzle.confirmed.get(i).copied().flatten());
+        write_statement(
+            &mut bytes,
+            puzzle
+                .observed
+                .get(i)
+                .unwrap_or(&RoleStatement::NoStatement),
+        );
+    }
+
+    to_base64(&bytes)
+}
+
+/// Reverse of `encode_puzzle`
+pub fn decode_puzzle(token: &str) -> Result<Puzzle, String> {
+    let bytes = from_base64(token.trim())?;
+    let mut reader = Reader { bytes, pos: 0 };
+
+    let version = reader.byte()?;
+    if version != FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported puzzle format version {}, expected {}",
+            version, FORMAT_VERSION
+        ));
+    }
+
+    let deck_len = reader.number()?;
+    let deck = (0..deck_len)
+        .map(|_| reader.role())
+        .collect::<Result<Vec<_>, _>>()?;
+    let villagers = reader.number()?;
+    let outcasts = reader.number()?;
+    let minions = reader.number()?;
+    let demons = reader.number()?;
+
+    let seats = reader.number()?;
+    let mut visible = Vec::new();
+    let mut confirmed = Vec::new();
+    let mut observed = Vec::new();
+    for _ in 0..seats {
+        visible.push(reader.optional_role()?);
+        confirmed.push(reader.optional_role()?);
+        observed.push(reader.statement()?);
+    }
+
+    if reader.pos != reader.bytes.len() {
+        return Err(format!(
+            "Puzzle token has {} unexpected trailing bytes",
+            reader.bytes.len() - reader.pos
+        ));
+    }
+
+    Ok(Puzzle {
+        deck,
+        visible,
+        confirmed,
+        observed,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    })
+}
+
+/// LEB128, so small counts and seat indexes take a single byte
+fn write_number(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let low = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(low);
+            return;
+        }
+        bytes.push(low | 0x80);
+    }
+}
+
+fn role_id(role: Role) -> usize {
+    ROLE_IDS
+        .iter()
+        .position(|&r| r == role)
+        .unwrap_or_else(|| panic!("{:?} has no number in ROLE_IDS", role))
+}
+
+fn write_role(bytes: &mut Vec<u8>, role: Role) {
+    write_number(bytes, role_id(role));
+}
+
+/// Shifted up by one so zero can stand for `None`
+fn write_optional_role(bytes: &mut Vec<u8>, role: Option<Role>) {
+    write_number(bytes, role.map_or(0, |role| role_id(role) + 1));
+}
+
+fn write_optional_number(bytes: &mut Vec<u8>, value: Option<usize>) {
+    write_number(bytes, value.map_or(0, |v| v + 1));
+}
+
+fn write_bits(bytes: &mut Vec<u8>, bits: &TargetIndexes) {
+    bytes.extend_from_slice(&bits.data);
+}
+
+fn write_statement(bytes: &mut Vec<u8>, statement: &RoleStatement) {
+    match statement {
+        RoleStatement::NoStatement => bytes.push(0),
+        RoleStatement::Alchemist(s) => {
+            bytes.push(1);
+            write_number(bytes, s.corrupt_count);
+        }
+        RoleStatement::Architect(s) => {
+            bytes.push(2);
+            bytes.push(match s {
+                ArchitectStatement::Right => 0,
+                ArchitectStatement::Left => 1,
+                ArchitectStatement::Equal => 2,
+            });
+        }
+        RoleStatement::Bard(s) => {
+            bytes.push(3);
+            write_optional_number(bytes, s.distance);
+        }
+        RoleStatement::Bishop(s) => {
+            bytes.push(4);
+            write_bits(bytes, &s.target_indexes);
+        }
+        RoleStatement::Confessor(s) => {
+            bytes.push(5);
+            bytes.push(match s {
+                ConfessorStatement::IAmGood => 0,
+                ConfessorStatement::IAmDizzy => 1,
+            });
+        }
+        RoleStatement::Druid(s) => {
+            bytes.push(6);
+            write_bits(bytes, &s.target_indexes);
+            write_optional_role(bytes, s.role);
+        }
+        RoleStatement::Dreamer(s) => {
+            bytes.push(if s.negated { 23 } else { 7 });
+            write_number(bytes, s.target_index);
+            write_role(bytes, s.role);
+        }
+        RoleStatement::Empress(s) => {
+            bytes.push(8);
+            write_bits(bytes, &s.target_indexes);
+        }
+        RoleStatement::Enlightened(s) => {
+            bytes.push(9);
+            bytes.push(match s {
+                EnlightenedStatement::Clockwise => 0,
+                EnlightenedStatement::CounterClockwise => 1,
+                EnlightenedStatement::Equidistant => 2,
+            });
+        }
+        RoleStatement::FortuneTeller(s) => {
+            bytes.push(10);
+            write_bits(bytes, &s.target_indexes);
+            bytes.push(s.is_evil as u8);
+        }
+        RoleStatement::Gemcrafter(s) => {
+            bytes.push(11);
+            write_number(bytes, s.target_index);
+        }
+        RoleStatement::Hunter(s) => {
+            bytes.push(12);
+            write_number(bytes, s.distance);
+        }
+        RoleStatement::Jester(s) => {
+            bytes.push(13);
+            write_bits(bytes, &s.target_indexes);
+            write_number(bytes, s.evil_count);
+        }
+        RoleStatement::Judge(s) => {
+            bytes.push(14);
+            write_number(bytes, s.target_index);
+            bytes.push(s.is_lying as u8);
+        }
+        RoleStatement::Knitter(s) => {
+            bytes.push(15);
+            write_number(bytes, s.adjacent_count);
+        }
+        RoleStatement::Lover(s) => {
+            bytes.push(16);
+            write_number(bytes, s.evil_count);
+        }
+        RoleStatement::Medium(s) => {
+            bytes.push(17);
+            write_number(bytes, s.target_index);
+            write_role(bytes, s.role);
+        }
+        RoleStatement::Oracle(s) => match s.claim {
+            OracleClaim::Role(role) => {
+                bytes.push(18);
+                write_bits(bytes, &s.target_indexes);
+                write_role(bytes, role);
+            }
+            // Added after the other kinds, so it goes last to keep older tokens valid
+            OracleClaim::Group(group) => {
+                bytes.push(22);
+                write_bits(bytes, &s.target_indexes);
+                bytes.push(match group {
+                    Group::Villager => 0,
+                    Group::Outcast => 1,
+                    Group::Minion => 2,
+                    Group::Demon => 3,
+                });
+            }
+        },
+        RoleStatement::Scout(s) => {
+            bytes.push(19);
+            write_optional_role(bytes, s.role);
+            write_number(bytes, s.distance);
+        }
+        RoleStatement::Slayer(s) => {
+            bytes.push(20);
+            write_number(bytes, s.target_index);
+            bytes.push(match s.alignment {
+                Alignment::Good => 0,
+                Alignment::Evil => 1,
+            });
+        }
+        RoleStatement::PlagueDoctor(s) => {
+            bytes.push(21);
+            write_number(bytes, s.corruption_index);
+            write_optional_number(bytes, s.evil_index);
+        }
+    }
+}
+
+struct Reader {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl Reader {
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| "Puzzle token ended early".to_string())?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn number(&mut self) -> Result<usize, String> {
+        let mut value = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Number in puzzle token is too large".to_string())
+    }
+
+    fn bool(&mut self) -> Result<bool, String> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(format!("Invalid flag {} in puzzle token", other)),
+        }
+    }
+
+    fn choice(&mut self, options: usize, what: &str) -> Result<u8, String> {
+        let value = self.byte()?;
+        if (value as usize) < options {
+            Ok(value)
+        } else {
+            Err(format!("Invalid {} {} in puzzle token", what, value))
+        }
+    }
+
+    fn role(&mut self) -> Result<Role, String> {
+        let index = self.number()?;
+        ROLE_IDS
+            .get(index)
+            .copied()
+            .ok_or_else(|| format!("Unknown role {} in puzzle token", index))
+    }
+
+    fn optional_role(&mut self) -> Result<Option<Role>, String> {
+        match self.number()? {
+            0 => Ok(None),
+            index => ROLE_IDS
+                .get(index - 1)
+                .copied()
+                .map(Some)
+                .ok_or_else(|| format!("Unknown role {} in puzzle token", index - 1)),
+        }
+    }
+
+    fn optional_number(&mut self) -> Result<Option<usize>, String> {
+        Ok(self.number()?.checked_sub(1))
+    }
+
+    fn bits(&mut self) -> Result<TargetIndexes, String> {
+        let mut bits = TargetIndexes::default();
+        for byte in bits.data.iter_mut() {
+            *byte = self.byte()?;
+        }
+        Ok(bits)
+    }
+
+    fn statement(&mut self) -> Result<RoleStatement, String> {
+        let statement = match self.byte()? {
+            0 => RoleStatement::NoStatement,
+            1 => AlchemistStatement {
+                corrupt_count: self.number()?,
+            }
+            .into(),
+            2 => match self.choice(3, "Architect statement")? {
+                0 => ArchitectStatement::Right,
+                1 => ArchitectStatement::Left,
+                _ => ArchitectStatement::Equal,
+            }
+            .into(),
+            3 => BardStatement {
+                distance: self.optional_number()?,
+            }
+            .into(),
+            4 => BishopStatement {
+                target_indexes: self.bits()?,
+            }
+            .into(),
+            5 => match self.choice(2, "Confessor statement")? {
+                0 => ConfessorStatement::IAmGood,
+                _ => ConfessorStatement::IAmDizzy,
+            }
+            .into(),
+            6 => DruidStatement {
+                target_indexes: self.bits()?,
+                role: self.optional_role()?,
+            }
+            .into(),
+            tag @ (7 | 23) => DreamerStatement {
+                target_index: self.number()?,
+                role: self.role()?,
+                negated: tag == 23,
+            }
+            .into(),
+            8 => EmpressStatement {
+                target_indexes: self.bits()?,
+            }
+            .into(),
+            9 => match self.choice(3, "Enlightened statement")? {
+                0 => EnlightenedStatement::Clockwise,
+                1 => EnlightenedStatement::CounterClockwise,
+                _ => EnlightenedStatement::Equidistant,
+            }
+            .into(),
+            10 => FortuneTellerStatement {
+                target_indexes: self.bits()?,
+                is_evil: self.bool()?,
+            }
+            .into(),
+            11 => GemcrafterStatement {
+                target_index: self.number()?,
+            }
+            .into(),
+            12 => HunterStatement {
+                distance: self.number()?,
+            }
+            .into(),
+            13 => JesterStatement {
+                target_indexes: self.bits()?,
+                evil_count: self.number()?,
+            }
+            .into(),
+            14 => JudgeStatement {
+                target_index: self.number()?,
+                is_lying: self.bool()?,
+            }
+            .into(),
+            15 => KnitterStatement {
+                adjacent_count: self.number()?,
+            }
+            .into(),
+            16 => LoverStatement {
+                evil_count: self.number()?,
+            }
+            .into(),
+            17 => MediumStatement {
+                target_index: self.number()?,
+                role: self.role()?,
+            }
+            .into(),
+            18 => OracleStatement {
+                target_indexes: self.bits()?,
+                claim: OracleClaim::Role(self.role()?),
+            }
+            .into(),
+            19 => ScoutStatement {
+                role: self.optional_role()?,
+                distance: self.number()?,
+            }
+            .into(),
+            20 => SlayerStatement {
+                target_index: self.number()?,
+                alignment: match self.choice(2, "Slayer alignment")? {
+                    0 => Alignment::Good,
+                    _ => Alignment::Evil,
+                },
+            }
+            .into(),
+            21 => PlagueDoctorStatement {
+                corruption_index: self.number()?,
+                evil_index: self.optional_number()?,
+            }
+            .into(),
+            22 => OracleStatement {
+                target_indexes: self.bits()?,
+                claim: OracleClaim::Group(match self.choice(4, "Oracle group")? {
+                    0 => Group::Villager,
+                    1 => Group::Outcast,
+                    2 => Group::Minion,
+                    _ => Group::Demon,
+                }),
+            }
+            .into(),
+            tag => return Err(format!("Unknown statement kind {} in puzzle token", tag)),
+        };
+        Ok(statement)
+    }
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let group = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        // A chunk of n bytes needs n + 1 characters once padding is dropped
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn from_base64(token: &str) -> Result<Vec<u8>, String> {
+    let values = token
+        .bytes()
+        .map(|c| {
+            ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .map(|v| v as u32)
+                .ok_or_else(|| format!("Invalid character '{}' in puzzle token", c as char))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        if chunk.len() == 1 {
+            return Err("Puzzle token has the wrong length".to_string());
+        }
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |group, (i, v)| group | v << (18 - 6 * i));
+        for i in 0..chunk.len() - 1 {
+            bytes.push((group >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,46 @@
+use crate::roles::*;
+
+/// One way the board can be seated, with what every seat shows
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Solution {
+    /// Real role of each seat
+    pub true_roles: Vec<Role>,
+    /// Role each seat shows. Most seats show their real role, but evil seats
+    /// can be disguised and a Drunk shows a villager. When a seat could show
+    /// several roles, solutions are sorted and only the smallest of each
+    /// seating is kept, so these are the disguises first in role order, seat
+    /// by seat.
+    pub disguised_roles: Vec<Role>,
+    /// Which seats are corrupted, in the first corruption spread that worked
+    /// with these disguises
+    pub corrupted: Vec<bool>,
+}
+
+impl Solution {
+    /// The role seat `i` shows when it isn't its real role, `None` otherwise
+    pub fn disguise_at(&self, i: usize) -> Option<Role> {
+        let disguise = *self.disguised_roles.get(i)?;
+        (disguise != self.true_roles[i]).then_some(disguise)
+    }
+}
+
+/// The seating as one line, with disguised seats followed by what they show and
+/// corrupted seats marked with `*`, e.g. `Knight, Poisoner(as Confessor), Lover*`
+pub fn format_solution_detailed(solution: &Solution) -> String {
+    solution
+        .true_roles
+        .iter()
+        .enumerate()
+        .map(|(i, role)| {
+            let mut seat = role.display_name().to_string();
+            if let Some(disguise) = solution.disguise_at(i) {
+                seat.push_str(&format!("(as {})", disguise.display_name()));
+            }
+            if solution.corrupted.get(i).copied().unwrap_or(false) {
+                seat.push('*');
+            }
+            seat
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
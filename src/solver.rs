@@ -1,8 +1,169 @@
+use crate::puzzle::Puzzle;
 use crate::roles::*;
+use crate::solution::{format_solution_detailed, Solution};
 use itertools::izip;
 use itertools::Itertools;
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often `brute_force_solve_with_progress` checks on the search
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Villager, outcast, minion and demon combinations to choose from
+type RoleCombinations = (
+    Vec<Vec<Role>>,
+    Vec<Vec<Role>>,
+    Vec<Vec<Role>>,
+    Vec<Vec<Role>>,
+);
+
+/// What the player knows about the board besides the statements, e.g. from
+/// revealed cards. Empty lists mean nothing is known.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Whether each seat is corrupted once Alchemists have cured, `None` if unknown
+    pub known_corruptions: Vec<Option<bool>>,
+    /// True alignment of each seat, `None` if unknown
+    pub confirmed_alignment: Vec<Option<Alignment>>,
+    /// True group of each seat, `None` if unknown
+    pub confirmed_group: Vec<Option<Group>>,
+    /// Roles each seat is known not to be, empty if nothing is ruled out
+    pub excluded_roles: Vec<Vec<Role>>,
+    /// Seats whose statement is known to be false, e.g. because they were
+    /// revealed as corrupted. Missing seats count as `false`.
+    pub force_lying: Vec<bool>,
+    /// Seats known not to be corrupted, the same as a `Some(false)` in
+    /// `known_corruptions`. Missing seats count as `false`.
+    pub uncorrupted: Vec<bool>,
+    /// How the seats are arranged, a circle unless told otherwise
+    pub topology: Topology,
+    /// The demon in play when it's known which one it is but not where it sits.
+    /// Every demon seat then holds this role.
+    pub known_demon: Option<Role>,
+}
+
+/// What a search did and how long it took, see `solve_with_stats`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Complete seatings generated
+    pub candidates: usize,
+    /// Seatings cut off early or dropped for going against a confirmed role,
+    /// alignment or group, or a role the seat is known not to be
+    pub pruned_by_confirmation: usize,
+    /// Seatings cut off early for putting a role on a seat it can't show the
+    /// visible role from
+    pub pruned_by_visible: usize,
+    /// Seatings that fit every statement, before duplicates are dropped
+    pub accepted: usize,
+    /// Wall-clock time of the whole solve
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for SolveStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Solved in {:.2?}: {} candidates, {} pruned by confirmed roles, {} pruned by visible roles, {} accepted",
+            self.elapsed,
+            self.candidates,
+            self.pruned_by_confirmation,
+            self.pruned_by_visible,
+            self.accepted
+        )
+    }
+}
+
+/// Why a puzzle can't be handed to the solver. Seats are counted from 0, but
+/// the messages number them from 1 like the rest of the output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The visible, confirmed and observed lists don't cover the same seats
+    LengthMismatch {
+        visible: usize,
+        confirmed: usize,
+        observed: usize,
+    },
+    /// The villager, outcast, minion and demon counts don't add up to the seats
+    CountSumMismatch { counts: usize, seats: usize },
+    /// The deck has fewer roles of a group than the counts put in play
+    DeckUndersupplied {
+        group: Group,
+        needed: usize,
+        available: usize,
+    },
+    /// A statement names a seat that doesn't exist
+    IndexOutOfRange { seat: usize, seats: usize },
+    /// A Bishop, Empress or Gemcrafter statement names the seat that made it
+    NamesSpeaker { seat: usize },
+    /// A seat shows a role its confirmed role can't disguise as
+    UnshowableConfirmed {
+        seat: usize,
+        confirmed: Role,
+        visible: Role,
+    },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::LengthMismatch {
+                visible,
+                confirmed,
+                observed,
+            } => write!(
+                f,
+                "Got {} visible roles, {} confirmed roles and {} statements, expected one of each per seat",
+                visible, confirmed, observed
+            ),
+            SolveError::CountSumMismatch { counts, seats } => write!(
+                f,
+                "The counts add up to {} but there are {} seats",
+                counts, seats
+            ),
+            SolveError::DeckUndersupplied {
+                group,
+                needed,
+                available,
+            } => write!(
+                f,
+                "{} {:?}s are in play but the deck only has {}",
+                needed, group, available
+            ),
+            SolveError::IndexOutOfRange { seat, seats } => write!(
+                f,
+                "Seat {} refers to a seat past the {} in play",
+                seat + 1,
+                seats
+            ),
+            SolveError::NamesSpeaker { seat } => write!(
+                f,
+                "Seat {} names its own seat, which its role never does",
+                seat + 1
+            ),
+            SolveError::UnshowableConfirmed {
+                seat,
+                confirmed,
+                visible,
+            } => write!(
+                f,
+                "Seat {} is confirmed as {} but shows {}, which it can't disguise as",
+                seat + 1,
+                confirmed.display_name(),
+                visible.display_name()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[allow(clippy::too_many_arguments)]
 pub fn brute_force_solve(
     deck: &[Role],
     visible_roles: &[Option<Role>],
@@ -12,38 +173,683 @@ pub fn brute_force_solve(
     outcasts: usize,
     minions: usize,
     demons: usize,
-    verbose: bool,
 ) -> Vec<Vec<Role>> {
+    brute_force_solve_constrained(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        &Constraints::default(),
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    )
+}
+
+/// Same as `brute_force_solve`, but returns an error for malformed input
+/// instead of panicking or quietly finding nothing
+#[allow(clippy::too_many_arguments)]
+pub fn try_solve(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> Result<Vec<Vec<Role>>, SolveError> {
+    try_solve_detailed(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    )
+    .map(true_roles)
+}
+
+/// `try_solve` that keeps the disguises and corruptions of every solution, like
+/// `brute_force_solve_detailed`
+#[allow(clippy::too_many_arguments)]
+pub fn try_solve_detailed(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> Result<Vec<Solution>, SolveError> {
+    check_solve_inputs(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    )?;
+    let combinations = generate_role_combinations(deck, villagers, outcasts, minions, demons);
+    Ok(search(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        &Constraints::default(),
+        &combinations,
+        &SearchHooks::default(),
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_solve_inputs(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> Result<(), SolveError> {
+    let seats = visible_roles.len();
+    if confirmed_roles.len() != seats || observed_statements.len() != seats {
+        return Err(SolveError::LengthMismatch {
+            visible: seats,
+            confirmed: confirmed_roles.len(),
+            observed: observed_statements.len(),
+        });
+    }
+
+    let counts = villagers + outcasts + minions + demons;
+    if counts != seats {
+        return Err(SolveError::CountSumMismatch { counts, seats });
+    }
+
+    for (group, needed) in [
+        (Group::Villager, villagers),
+        (Group::Outcast, outcasts),
+        (Group::Minion, minions),
+        (Group::Demon, demons),
+    ] {
+        let available = deck.iter().filter(|r| r.group() == group).count();
+        if available < needed {
+            return Err(SolveError::DeckUndersupplied {
+                group,
+                needed,
+                available,
+            });
+        }
+    }
+
+    if let Some(seat) = observed_statements
+        .iter()
+        .position(|statement| !targets_in_range(statement, seats))
+    {
+        return Err(SolveError::IndexOutOfRange { seat, seats });
+    }
+
+    if let Some(seat) = observed_statements
+        .iter()
+        .enumerate()
+        .position(|(seat, statement)| names_speaker(statement, seat))
+    {
+        return Err(SolveError::NamesSpeaker { seat });
+    }
+
+    for (seat, (confirmed, visible)) in confirmed_roles.iter().zip(visible_roles).enumerate() {
+        if let (Some(confirmed), Some(visible)) = (*confirmed, *visible) {
+            if !can_show_as(deck, confirmed, visible) {
+                return Err(SolveError::UnshowableConfirmed {
+                    seat,
+                    confirmed,
+                    visible,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `check_solve_inputs` for the solvers that panic on malformed input. Input
+/// that is well formed but can't come up in game, a statement naming its own
+/// seat or a confirmed role that can't show as the visible one, isn't a panic:
+/// it has no solutions. Returns whether there's anything to search.
+#[allow(clippy::too_many_arguments)]
+fn check_or_panic(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> bool {
+    match check_solve_inputs(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    ) {
+        Ok(()) => true,
+        Err(SolveError::NamesSpeaker { .. } | SolveError::UnshowableConfirmed { .. }) => false,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Same as `brute_force_solve`, but only keeps boards that agree with `constraints`
+#[allow(clippy::too_many_arguments)]
+pub fn brute_force_solve_constrained(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    constraints: &Constraints,
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> Vec<Vec<Role>> {
+    if !check_or_panic(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    ) {
+        return Vec::new();
+    }
+    let combinations = generate_role_combinations(deck, villagers, outcasts, minions, demons);
+    true_roles(search(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        constraints,
+        &combinations,
+        &SearchHooks::default(),
+    ))
+}
+
+/// `brute_force_solve` for each of `puzzles`, in the same order. The puzzles are
+/// solved in parallel with each other, each one on a single thread, which
+/// keeps the cores busy when there are many small puzzles.
+pub fn solve_batch(puzzles: &[Puzzle]) -> Vec<Vec<Vec<Role>>> {
+    puzzles
+        .par_iter()
+        .map(|puzzle| {
+            if !check_or_panic(
+                &puzzle.deck,
+                &puzzle.visible,
+                &puzzle.confirmed,
+                &puzzle.observed,
+                puzzle.villagers,
+                puzzle.outcasts,
+                puzzle.minions,
+                puzzle.demons,
+            ) {
+                return Vec::new();
+            }
+            let combinations = generate_role_combinations(
+                &puzzle.deck,
+                puzzle.villagers,
+                puzzle.outcasts,
+                puzzle.minions,
+                puzzle.demons,
+            );
+            let hooks = SearchHooks {
+                serial: true,
+                ..Default::default()
+            };
+            true_roles(search(
+                &puzzle.deck,
+                &puzzle.visible,
+                &puzzle.confirmed,
+                &puzzle.observed,
+                &Constraints::default(),
+                &combinations,
+                &hooks,
+            ))
+        })
+        .collect()
+}
+
+/// Same as `brute_force_solve`, but also tells what each seat shows in every
+/// solution, e.g. which villager an evil seat is disguised as
+#[allow(clippy::too_many_arguments)]
+pub fn brute_force_solve_detailed(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> Vec<Solution> {
+    if !check_or_panic(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    ) {
+        return Vec::new();
+    }
+    let combinations = generate_role_combinations(deck, villagers, outcasts, minions, demons);
+    search(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        &Constraints::default(),
+        &combinations,
+        &SearchHooks::default(),
+    )
+}
+
+/// The deck and counts of a game, with the role combinations worked out once
+/// so boards that only differ in what the seats show or say can be solved
+/// again without redoing them.
+pub struct SolverContext {
+    deck: Vec<Role>,
+    counts: [usize; 4],
+    combinations: RoleCombinations,
+}
+
+impl SolverContext {
+    pub fn new(
+        deck: &[Role],
+        villagers: usize,
+        outcasts: usize,
+        minions: usize,
+        demons: usize,
+    ) -> Self {
+        SolverContext {
+            deck: deck.to_vec(),
+            counts: [villagers, outcasts, minions, demons],
+            combinations: generate_role_combinations(deck, villagers, outcasts, minions, demons),
+        }
+    }
+
+    /// Same as `brute_force_solve` with this context's deck and counts
+    pub fn solve_with(
+        &self,
+        visible_roles: &[Option<Role>],
+        confirmed_roles: &[Option<Role>],
+        observed_statements: &[RoleStatement],
+    ) -> Vec<Vec<Role>> {
+        let [villagers, outcasts, minions, demons] = self.counts;
+        if !check_or_panic(
+            &self.deck,
+            visible_roles,
+            confirmed_roles,
+            observed_statements,
+            villagers,
+            outcasts,
+            minions,
+            demons,
+        ) {
+            return Vec::new();
+        }
+        self.search(visible_roles, confirmed_roles, observed_statements)
+    }
+
+    /// Same as `try_solve` with this context's deck and counts
+    pub fn try_solve_with(
+        &self,
+        visible_roles: &[Option<Role>],
+        confirmed_roles: &[Option<Role>],
+        observed_statements: &[RoleStatement],
+    ) -> Result<Vec<Vec<Role>>, SolveError> {
+        let [villagers, outcasts, minions, demons] = self.counts;
+        check_solve_inputs(
+            &self.deck,
+            visible_roles,
+            confirmed_roles,
+            observed_statements,
+            villagers,
+            outcasts,
+            minions,
+            demons,
+        )?;
+        Ok(self.search(visible_roles, confirmed_roles, observed_statements))
+    }
+
+    fn search(
+        &self,
+        visible_roles: &[Option<Role>],
+        confirmed_roles: &[Option<Role>],
+        observed_statements: &[RoleStatement],
+    ) -> Vec<Vec<Role>> {
+        true_roles(search(
+            &self.deck,
+            visible_roles,
+            confirmed_roles,
+            observed_statements,
+            &Constraints::default(),
+            &self.combinations,
+            &SearchHooks::default(),
+        ))
+    }
+}
+
+/// Same as `brute_force_solve`, but calls `progress` with `(finished, total)`
+/// villager combinations every now and then while the search runs.
+#[allow(clippy::too_many_arguments)]
+pub fn brute_force_solve_with_progress<F>(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+    mut progress: F,
+) -> Vec<Vec<Role>>
+where
+    F: FnMut(usize, usize),
+{
+    if !check_or_panic(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    ) {
+        return Vec::new();
+    }
+    let combinations = generate_role_combinations(deck, villagers, outcasts, minions, demons);
+    let total = combinations.0.len();
+    let hooks = SearchHooks::default();
+
+    thread::scope(|scope| {
+        let search_thread = scope.spawn(|| {
+            search(
+                deck,
+                visible_roles,
+                confirmed_roles,
+                observed_statements,
+                &Constraints::default(),
+                &combinations,
+                &hooks,
+            )
+        });
+
+        // Only report when something changed, the search itself never waits on this
+        let mut reported = 0;
+        loop {
+            let done = search_thread.is_finished();
+            let current = hooks.finished.load(Ordering::Relaxed);
+            if current != reported {
+                reported = current;
+                progress(current, total);
+            }
+            if done {
+                break;
+            }
+            thread::sleep(PROGRESS_INTERVAL);
+        }
+
+        true_roles(search_thread.join().expect("Solver thread panicked"))
+    })
+}
+
+/// Same as `brute_force_solve`, but hands out solutions as the search finds
+/// them, so callers can stop early with e.g. `.take(10)`. The search runs on a
+/// background thread that stops once the iterator is dropped. Solutions come
+/// in whatever order the threads find them, sort them if order matters.
+#[allow(clippy::too_many_arguments)]
+pub fn solve_iter(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> impl Iterator<Item = Vec<Role>> {
+    stream_solutions(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+        None,
+    )
+}
+
+/// Same as `solve_iter`, but tries villager and minion combinations in an
+/// order shuffled by `seed`. Deck order puts late roles last, so when only
+/// the first few solutions are wanted a shuffle finds them sooner on average.
+/// Running it to the end yields the same solutions as `solve_iter`. The
+/// search runs on a single thread, so a seed always gives the same sequence.
+#[allow(clippy::too_many_arguments)]
+pub fn solve_iter_shuffled(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+    seed: u64,
+) -> impl Iterator<Item = Vec<Role>> {
+    stream_solutions(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+        Some(seed),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn stream_solutions(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+    shuffle_seed: Option<u64>,
+) -> impl Iterator<Item = Vec<Role>> {
+    // Checked here rather than on the search thread, so a panic reaches the caller
+    let searchable = check_or_panic(
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+    );
+    let deck = deck.to_vec();
+    let visible_roles = visible_roles.to_vec();
+    let confirmed_roles = confirmed_roles.to_vec();
+    let observed_statements = observed_statements.to_vec();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        // Dropping the sender ends the iterator without any solutions
+        if !searchable {
+            return;
+        }
+        let mut combinations =
+            generate_role_combinations(&deck, villagers, outcasts, minions, demons);
+        if let Some(seed) = shuffle_seed {
+            let mut state = seed;
+            shuffle(&mut combinations.0, &mut state);
+            shuffle(&mut combinations.2, &mut state);
+        }
+        // Parallel workers would race each other to the sink, so a seeded
+        // search stays on this thread to give the same order every time
+        let hooks = SearchHooks {
+            sink: Some(sender),
+            serial: shuffle_seed.is_some(),
+            ..Default::default()
+        };
+        search(
+            &deck,
+            &visible_roles,
+            &confirmed_roles,
+            &observed_statements,
+            &Constraints::default(),
+            &combinations,
+            &hooks,
+        );
+    });
+
+    // The same seating can be reached through different role variations
+    let mut seen = HashSet::new();
+    receiver
+        .into_iter()
+        .map(|solution: Solution| solution.true_roles)
+        .filter(move |solution| seen.insert(solution.clone()))
+}
+
+/// Fisher-Yates with a xorshift generator, so a seed always gives the same order
+fn shuffle<T>(items: &mut [T], state: &mut u64) {
+    // Xorshift gets stuck on zero
+    if *state == 0 {
+        *state = 0x9e37_79b9_7f4a_7c15;
+    }
+    for i in (1..items.len()).rev() {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        let j = (*state % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Ways for callers to follow along with `search` while it runs
+#[derive(Default)]
+struct SearchHooks {
+    /// Villager combinations searched so far
+    finished: AtomicUsize,
+    /// Per seat count of seatings its statement ruled out, left empty to not count
+    rejections: Vec<AtomicUsize>,
+    /// Sends each solution here as soon as it's found instead of collecting them
+    sink: Option<Sender<Solution>>,
+    /// Set once nobody listens to the sink anymore, the search then winds down
+    cancelled: AtomicBool,
+    /// Tallies of what the search did, left out to not count
+    counters: Option<SearchCounters>,
+    /// Search on the calling thread only, for when the caller already runs
+    /// several searches in parallel
+    serial: bool,
+}
+
+/// The counts behind `SolveStats`, shared between the search threads
+#[derive(Default)]
+struct SearchCounters {
+    candidates: AtomicUsize,
+    pruned_by_confirmation: AtomicUsize,
+    pruned_by_visible: AtomicUsize,
+    accepted: AtomicUsize,
+}
+
+impl SearchHooks {
+    fn count(&self, counter: impl Fn(&SearchCounters) -> &AtomicUsize) {
+        if let Some(counters) = &self.counters {
+            counter(counters).fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn search(
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    constraints: &Constraints,
+    combinations: &RoleCombinations,
+    hooks: &SearchHooks,
+) -> Vec<Solution> {
     assert_eq!(
         visible_roles.len(),
         observed_statements.len(),
         "visible_roles and observed_statements must match"
     );
     let n = visible_roles.len();
-    let has_puppet = deck.iter().any(|&r| r == Role::Puppet);
-
-    // Pre-generate all possible role group combinations based on counts requested
-    let (villager_combos, outcast_combos, minion_combos, demon_combos) =
-        generate_role_combinations(deck, villagers, outcasts, minions, demons);
-
-    // Wretch needs to be replaced with any minion from the deck
-    let deck_minions: Vec<Role> = deck
+    let has_puppet = deck.contains(&Role::Puppet);
+    let (villager_combos, outcast_combos, minion_combos, demon_combos) = combinations;
+    // Only the known demon needs trying, if there is one
+    let demon_combos: Vec<&Vec<Role>> = demon_combos
         .iter()
-        .copied()
-        .filter(|r| r.group() == Group::Minion)
+        .filter(|combo| {
+            constraints
+                .known_demon
+                .is_none_or(|demon| combo.iter().all(|&role| role == demon))
+        })
         .collect();
 
+    // Wretch needs to be replaced with any minion from the deck
+    let deck_minions = wretch_registrations(deck, observed_statements);
+
     // Disguised minions can appear as any non-evil role (and also not wretch)
     let deck_non_evil: Vec<Role> = deck
         .iter()
         .copied()
-        .filter(|r| r.alignment() != Alignment::Evil && *r != Role::Wretch)
+        .filter(|r| !r.alignment().is_evil() && *r != Role::Wretch)
         .collect();
 
+    // With nothing shown, said or known about corruption, whether a set of roles
+    // can be disguised doesn't depend on how it's seated
+    let seating_independent =
+        disguises_seating_independent(visible_roles, observed_statements, constraints);
+
     // Try every possible combination of villagers, minions, and outcasts
-    villager_combos
+    // Serial searches keep all combinations in one piece so rayon never splits them
+    let min_len = if hooks.serial {
+        villager_combos.len().max(1)
+    } else {
+        1
+    };
+    let mut solutions: Vec<Solution> = villager_combos
         .par_iter()
+        .with_min_len(min_len)
         .flat_map(|v_combo| {
+            if hooks.cancelled.load(Ordering::Relaxed) {
+                return Vec::new();
+            }
+
             let deck_villager_not_in_play: Vec<Role> = deck
                 .iter()
                 .copied()
@@ -55,16 +861,16 @@ pub fn brute_force_solve(
             let mut wretch_assign: Vec<Role> = Vec::with_capacity(n);
             let mut disguise_assign: Vec<Role> = Vec::with_capacity(n);
 
-            for o_combo in &outcast_combos {
+            for o_combo in outcast_combos {
                 let outcasts_not_in_play: Vec<Role> = deck
                     .iter()
                     .copied()
                     .filter(|r| r.group() == Group::Outcast && !o_combo.contains(r))
                     .collect();
-                for m_combo in &minion_combos {
-                    let has_counsellor = m_combo.iter().any(|&r| r == Role::Counsellor);
-                    let has_shaman = m_combo.iter().any(|&r| r == Role::Shaman);
-                    for d_combo in &demon_combos {
+                for m_combo in minion_combos {
+                    let has_counsellor = m_combo.contains(&Role::Counsellor);
+                    let has_shaman = m_combo.contains(&Role::Shaman);
+                    for &d_combo in &demon_combos {
                         let combined_variations = generate_role_variations(
                             v_combo,
                             o_combo,
@@ -88,7 +894,64 @@ pub fn brute_force_solve(
                                 *counts.entry(r).or_insert(0) += 1;
                             }
 
-                            let keys: Vec<Role> = counts.keys().copied().collect();
+                            // In order of appearance, so seatings come out in the same
+                            // order on every run rather than in hash order
+                            let keys: Vec<Role> = combined.iter().copied().unique().collect();
+
+                            // A seat can only hold its confirmed role, and a seat showing a
+                            // role only something that can show up as it
+                            let can_sit = |seat: usize, role: Role| {
+                                if confirmed_roles[seat].is_some_and(|confirmed| confirmed != role) {
+                                    hooks.count(|c| &c.pruned_by_confirmation);
+                                    return false;
+                                }
+                                let shows = visible_roles[seat].is_none_or(|shown| {
+                                    disguise_pool(
+                                        role,
+                                        &deck_non_evil,
+                                        &villagers_in_play,
+                                        &deck_villager_not_in_play,
+                                    )
+                                    .map_or(role == shown, |pool| pool.contains(&shown))
+                                });
+                                if !shows {
+                                    hooks.count(|c| &c.pruned_by_visible);
+                                }
+                                shows
+                            };
+
+                            // Disguise the roles once, seatings only shuffle them
+                            let shared_disguises = if seating_independent {
+                                let (wretch_choices, disguise_choices) = build_choices(
+                                    &combined,
+                                    &deck_minions,
+                                    &deck_non_evil,
+                                    &villagers_in_play,
+                                    &deck_villager_not_in_play,
+                                );
+                                let mut found = None;
+                                assign_disguises_and_check(
+                                    &combined,
+                                    deck,
+                                    &wretch_choices,
+                                    &disguise_choices,
+                                    visible_roles,
+                                    &mut wretch_assign,
+                                    &mut disguise_assign,
+                                    0,
+                                    &mut |wretches: &[Role], disguises: &[Role]| {
+                                        found = Some((wretches.to_vec(), disguises.to_vec()));
+                                        true
+                                    },
+                                );
+                                // No seating of these roles can be disguised
+                                if found.is_none() {
+                                    continue;
+                                }
+                                found
+                            } else {
+                                None
+                            };
 
                             // Generate all seat permutations of this role multiset
                             permute_multiset(
@@ -96,11 +959,62 @@ pub fn brute_force_solve(
                                 &keys,
                                 &mut perm_current,
                                 n,
-                                has_counsellor,
                                 has_puppet,
+                                constraints.topology,
+                                &can_sit,
                                 &mut |candidate: &[Role]| {
-                                    // Immediately discard if known confirmed roles don’t match
-                                    if !confirmed_roles_ok(candidate, confirmed_roles) {
+                                    if hooks.cancelled.load(Ordering::Relaxed) {
+                                        return;
+                                    }
+                                    hooks.count(|c| &c.candidates);
+
+                                    // Immediately discard if known constraints don’t match
+                                    if !confirmed_alignment_ok(candidate, &constraints.confirmed_alignment)
+                                        || !confirmed_group_ok(candidate, &constraints.confirmed_group)
+                                        || !excluded_roles_ok(candidate, &constraints.excluded_roles)
+                                    {
+                                        hooks.count(|c| &c.pruned_by_confirmation);
+                                        return;
+                                    }
+
+                                    // If the resulting seating matches all observed statements, keep it
+                                    let mut check = |full_wretch_assign: &[Role], full_disguise_assign: &[Role]| {
+                                        match statements_match(
+                                            candidate,
+                                            full_wretch_assign,
+                                            full_disguise_assign,
+                                            observed_statements,
+                                            constraints,
+                                        ) {
+                                            Ok(corrupted) => {
+                                                hooks.count(|c| &c.accepted);
+                                                let solution = Solution {
+                                                    true_roles: candidate.to_vec(),
+                                                    disguised_roles: full_disguise_assign.to_vec(),
+                                                    corrupted,
+                                                };
+                                                match &hooks.sink {
+                                                    Some(sink) => {
+                                                        if sink.send(solution).is_err() {
+                                                            hooks.cancelled.store(true, Ordering::Relaxed);
+                                                        }
+                                                    }
+                                                    None => local_valid.push(solution),
+                                                }
+                                                true
+                                            }
+                                            Err(seat) => {
+                                                if let Some(count) = seat.and_then(|seat| hooks.rejections.get(seat)) {
+                                                    count.fetch_add(1, Ordering::Relaxed);
+                                                }
+                                                false
+                                            }
+                                        }
+                                    };
+
+                                    if let Some((wretches, disguises)) = &shared_disguises {
+                                        let (wretches, disguises) = reseat(&combined, wretches, disguises, candidate);
+                                        check(&wretches, &disguises);
                                         return;
                                     }
 
@@ -115,26 +1029,14 @@ pub fn brute_force_solve(
                                     // DFS through every possible Wretch assignment + disguise mapping
                                     assign_disguises_and_check(
                                         candidate,
+                                        deck,
                                         &wretch_choices,
                                         &disguise_choices,
                                         visible_roles,
                                         &mut wretch_assign,
                                         &mut disguise_assign,
                                         0,
-                                        &mut |full_wretch_assign: &[Role], full_disguise_assign: &[Role]| {
-                                            // If the resulting seating matches all observed statements, keep it
-                                            let success = statements_match(
-                                                candidate,
-                                                full_wretch_assign,
-                                                full_disguise_assign,
-                                                observed_statements,
-                                                verbose
-                                            );
-                                            if success {
-                                                local_valid.push(candidate.to_vec());
-                                            }
-                                            success
-                                        },
+                                        &mut check,
                                     );
                                 },
                             );
@@ -143,11 +1045,390 @@ pub fn brute_force_solve(
                 }
             }
 
+            hooks.finished.fetch_add(1, Ordering::Relaxed);
             local_valid
         })
+        .collect();
+
+    // Rayon hands results back in any order, sort so runs are reproducible. The
+    // same seating can show up with different disguises, only the smallest is kept.
+    solutions.sort();
+    solutions.dedup_by(|a, b| a.true_roles == b.true_roles);
+    solutions
+}
+
+fn true_roles(solutions: Vec<Solution>) -> Vec<Vec<Role>> {
+    solutions
+        .into_iter()
+        .map(|solution| solution.true_roles)
+        .collect()
+}
+
+/// Same as `try_solve_detailed` for `puzzle`, but also counts what the search
+/// did and times it
+pub fn solve_with_stats(puzzle: &Puzzle) -> Result<(Vec<Solution>, SolveStats), SolveError> {
+    let started = Instant::now();
+    check_solve_inputs(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    )?;
+    let combinations = generate_role_combinations(
+        &puzzle.deck,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    );
+    let hooks = SearchHooks {
+        counters: Some(SearchCounters::default()),
+        ..Default::default()
+    };
+
+    let solutions = search(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        &Constraints::default(),
+        &combinations,
+        &hooks,
+    );
+
+    let counters = hooks.counters.unwrap_or_default();
+    let stats = SolveStats {
+        candidates: counters.candidates.into_inner(),
+        pruned_by_confirmation: counters.pruned_by_confirmation.into_inner(),
+        pruned_by_visible: counters.pruned_by_visible.into_inner(),
+        accepted: counters.accepted.into_inner(),
+        elapsed: started.elapsed(),
+    };
+    Ok((solutions, stats))
+}
+
+/// For a puzzle without solutions, which seats' statements ruled out the most
+/// seatings. Each seating is blamed on the first seat that contradicted it (in
+/// the corruption spread that held up the longest), and the result is
+/// `(seat, count)` sorted by count, most contradicted first.
+pub fn diagnose_infeasible(puzzle: &Puzzle) -> Vec<(usize, usize)> {
+    if !check_or_panic(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    ) {
+        return Vec::new();
+    }
+    let combinations = generate_role_combinations(
+        &puzzle.deck,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    );
+    let hooks = SearchHooks {
+        rejections: (0..puzzle.visible.len())
+            .map(|_| AtomicUsize::new(0))
+            .collect(),
+        ..Default::default()
+    };
+
+    search(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        &Constraints::default(),
+        &combinations,
+        &hooks,
+    );
+
+    let mut ranked: Vec<(usize, usize)> = hooks
+        .rejections
+        .into_iter()
+        .map(AtomicUsize::into_inner)
+        .enumerate()
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Seats whose statement can't be true with `minions` minions and `demons`
+/// demons in play, so whoever made it is lying or corrupted. Every minion and
+/// demon is evil, so a Scout saying there's only 1 Evil is wrong once two are
+/// in play.
+pub fn forced_liars(observed: &[RoleStatement], minions: usize, demons: usize) -> Vec<usize> {
+    observed
+        .iter()
+        .enumerate()
+        .filter(|(_, statement)| {
+            matches!(
+                statement,
+                RoleStatement::Scout(ScoutStatement { role: None, .. })
+            ) && minions + demons > 1
+        })
+        .map(|(seat, _)| seat)
+        .collect()
+}
+
+/// Every `(seat, role, count)` a demon sits at across `solutions`, with how
+/// many of them put it there. Sorted by count, most common first.
+pub fn group_by_demon(solutions: &[Vec<Role>]) -> Vec<(usize, Role, usize)> {
+    let mut counts: HashMap<(usize, Role), usize> = HashMap::new();
+    for solution in solutions {
+        for (seat, &role) in solution.iter().enumerate() {
+            if role.group() == Group::Demon {
+                *counts.entry((seat, role)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut grouped: Vec<(usize, Role, usize)> = counts
+        .into_iter()
+        .map(|((seat, role), count)| (seat, role, count))
+        .collect();
+    grouped.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+    grouped
+}
+
+/// For each seat a demon sits at in some solution of `puzzle`, how many
+/// solutions put one there, sorted by seat. Tallies solutions as `solve_iter`
+/// finds them rather than collecting every board first.
+pub fn demon_seat_distribution(puzzle: &Puzzle) -> Vec<(usize, usize)> {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for solution in solve_iter(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    ) {
+        for (seat, role) in solution.iter().enumerate() {
+            if role.group() == Group::Demon {
+                *counts.entry(seat).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut distribution: Vec<(usize, usize)> = counts.into_iter().collect();
+    distribution.sort();
+    distribution
+}
+
+/// Boards that are the same up to turning the circle, each given as its
+/// lexicographically smallest rotation. Only useful when no seat is pinned
+/// down, otherwise rotations aren't interchangeable.
+pub fn distinct_up_to_rotation(solutions: &[Vec<Role>]) -> Vec<Vec<Role>> {
+    let mut canonical: Vec<Vec<Role>> = solutions
+        .iter()
+        .map(|solution| {
+            (0..solution.len())
+                .map(|shift| {
+                    let mut rotated = solution.clone();
+                    rotated.rotate_left(shift);
+                    rotated
+                })
+                .min()
+                .unwrap_or_default()
+        })
+        .collect();
+    canonical.sort();
+    canonical.dedup();
+    canonical
+}
+
+/// For each seat, the alignment it has in every one of `solutions`, or `None`
+/// if the solutions disagree
+pub fn definite_alignments(solutions: &[Vec<Role>]) -> Vec<Option<Alignment>> {
+    let Some(first) = solutions.first() else {
+        return Vec::new();
+    };
+    (0..first.len())
+        .map(|seat| {
+            let alignment = first[seat].alignment();
+            solutions
+                .iter()
+                .all(|solution| solution[seat].alignment() == alignment)
+                .then_some(alignment)
+        })
         .collect()
 }
 
+/// The roles one seat has across a set of solutions, see `summarize_positions`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionSummary {
+    /// The seat, numbered from 0
+    pub index: usize,
+    /// Every role the seat has in some solution, sorted and without duplicates
+    pub possible: Vec<Role>,
+}
+
+impl PositionSummary {
+    /// The alignments of the possible roles, in the order they first show up
+    pub fn possible_alignments(&self) -> Vec<Alignment> {
+        self.possible
+            .iter()
+            .map(|role| role.alignment())
+            .unique()
+            .collect()
+    }
+
+    /// The groups of the possible roles, in the order they first show up
+    pub fn possible_groups(&self) -> Vec<Group> {
+        self.possible
+            .iter()
+            .map(|role| role.group())
+            .unique()
+            .collect()
+    }
+}
+
+/// For each seat, the roles it has in any of `solutions`
+pub fn summarize_positions(solutions: &[Vec<Role>]) -> Vec<PositionSummary> {
+    let Some(first) = solutions.first() else {
+        return Vec::new();
+    };
+    (0..first.len())
+        .map(|index| {
+            let mut possible: Vec<Role> =
+                solutions.iter().map(|solution| solution[index]).collect();
+            possible.sort();
+            possible.dedup();
+            PositionSummary { index, possible }
+        })
+        .collect()
+}
+
+/// Every statement `visible_role` at `seat` could truthfully make in at least
+/// one of `solutions`, in the order the solutions first allow them. Covers the
+/// same roles as `possible_statements`.
+pub fn consistent_statements_for_seat(
+    solutions: &[Solution],
+    seat: usize,
+    visible_role: Role,
+) -> Vec<RoleStatement> {
+    consistent_statements_for_seat_on(solutions, seat, visible_role, Topology::Circle)
+}
+
+/// Same as `consistent_statements_for_seat`, for solutions found with the
+/// seats arranged as `topology`
+pub fn consistent_statements_for_seat_on(
+    solutions: &[Solution],
+    seat: usize,
+    visible_role: Role,
+    topology: Topology,
+) -> Vec<RoleStatement> {
+    let mut statements: Vec<RoleStatement> = Vec::new();
+    for solution in solutions {
+        for statement in possible_statements_on(
+            visible_role,
+            &solution.true_roles,
+            &solution.disguised_roles,
+            &solution.corrupted,
+            seat,
+            topology,
+        ) {
+            if !statements.contains(&statement) {
+                statements.push(statement);
+            }
+        }
+    }
+    statements
+}
+
+/// Roles of `deck` that no seat has in any of `solutions`, in deck order. The
+/// solutions hold true roles, so a minion counts as used even where it only
+/// shows as a villager.
+pub fn unused_deck_roles(deck: &[Role], solutions: &[Vec<Role>]) -> Vec<Role> {
+    deck.iter()
+        .copied()
+        .unique()
+        .filter(|role| !solutions.iter().any(|solution| solution.contains(role)))
+        .collect()
+}
+
+/// Statements that make `board` the only solution, for writing puzzles. Every
+/// good seat shows its role and may make any statement it could truthfully make,
+/// each round adds the one that leaves the fewest solutions. Stops early with
+/// what it has if no statement narrows things down any further.
+pub fn minimal_statement_set(
+    board: &[Role],
+    deck: &[Role],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+) -> Vec<(usize, RoleStatement)> {
+    let n = board.len();
+    let corruptions = vec![false; n];
+    let options: Vec<(usize, RoleStatement)> = board
+        .iter()
+        .enumerate()
+        .filter(|(_, role)| role.alignment() == Alignment::Good)
+        .flat_map(|(i, &role)| {
+            possible_statements(role, board, board, &corruptions, i)
+                .into_iter()
+                .map(move |statement| (i, statement))
+        })
+        .collect();
+
+    // How many solutions are left once the chosen seats have spoken, `None` if
+    // that rules out the board itself
+    let remaining = |chosen: &[(usize, RoleStatement)]| {
+        let mut visible = vec![None; n];
+        let mut observed = vec![RoleStatement::NoStatement; n];
+        for (i, statement) in chosen {
+            visible[*i] = Some(board[*i]);
+            observed[*i] = statement.clone();
+        }
+        let confirmed = vec![None; n];
+        let solutions = try_solve(
+            deck, &visible, &confirmed, &observed, villagers, outcasts, minions, demons,
+        )
+        .ok()?;
+        solutions
+            .iter()
+            .any(|solution| solution == board)
+            .then_some(solutions.len())
+    };
+
+    let mut chosen: Vec<(usize, RoleStatement)> = Vec::new();
+    let mut left = remaining(&chosen).unwrap_or(0);
+    while left > 1 {
+        let best = options
+            .iter()
+            .filter(|(i, _)| chosen.iter().all(|(seat, _)| seat != i))
+            .filter_map(|option| {
+                let mut next = chosen.clone();
+                next.push(option.clone());
+                Some((remaining(&next)?, option))
+            })
+            .min_by_key(|(count, _)| *count);
+        match best {
+            Some((count, option)) if count < left => {
+                chosen.push(option.clone());
+                left = count;
+            }
+            _ => break,
+        }
+    }
+    chosen
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn validate_candidate(
     candidate: &[Role],
     deck: &[Role],
@@ -158,6 +1439,34 @@ pub fn validate_candidate(
     outcasts: usize,
     minions: usize,
     demons: usize,
+) -> Result<(), Vec<String>> {
+    validate_candidate_on(
+        candidate,
+        deck,
+        visible_roles,
+        confirmed_roles,
+        observed_statements,
+        villagers,
+        outcasts,
+        minions,
+        demons,
+        Topology::Circle,
+    )
+}
+
+/// Same as `validate_candidate`, with the seats arranged as `topology`
+#[allow(clippy::too_many_arguments)]
+pub fn validate_candidate_on(
+    candidate: &[Role],
+    deck: &[Role],
+    visible_roles: &[Option<Role>],
+    confirmed_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    villagers: usize,
+    outcasts: usize,
+    minions: usize,
+    demons: usize,
+    topology: Topology,
 ) -> Result<(), Vec<String>> {
     let mut rejection_reasons = Vec::new();
     let n = candidate.len();
@@ -179,7 +1488,7 @@ pub fn validate_candidate(
         *candidate_counts.entry(role).or_insert(0) += 1;
     }
 
-    let has_shaman = candidate.iter().any(|&r| r == Role::Shaman);
+    let has_shaman = candidate.contains(&Role::Shaman);
     let mut has_duplicate = false;
     for (role, &count_in_candidate) in &candidate_counts {
         let count_in_deck = deck_counts.get(role).copied().unwrap_or(0);
@@ -203,7 +1512,7 @@ pub fn validate_candidate(
     }
 
     if has_shaman && !has_duplicate {
-        rejection_reasons.push(format!("Game has shaman, but no duplicate was found"));
+        rejection_reasons.push("Game has shaman, but no duplicate was found".to_string());
     }
 
     // 3. Check role counts match the requested composition
@@ -269,8 +1578,8 @@ pub fn validate_candidate(
     }
 
     // 5. Puppet/Puppeteer constraints
-    let has_puppet = candidate.iter().any(|&r| r == Role::Puppet);
-    let has_puppeteer = candidate.iter().any(|&r| r == Role::Puppeteer);
+    let has_puppet = candidate.contains(&Role::Puppet);
+    let has_puppeteer = candidate.contains(&Role::Puppeteer);
 
     if has_puppet && !has_puppeteer {
         rejection_reasons.push("Puppet present without Puppeteer".to_string());
@@ -311,16 +1620,12 @@ pub fn validate_candidate(
     if !rejection_reasons.is_empty() {
         return Err(rejection_reasons);
     }
-    let deck_minions: Vec<Role> = deck
-        .iter()
-        .copied()
-        .filter(|r| r.group() == Group::Minion)
-        .collect();
+    let deck_minions = wretch_registrations(deck, observed_statements);
 
     let deck_non_evil: Vec<Role> = deck
         .iter()
         .copied()
-        .filter(|r| r.alignment() != Alignment::Evil && *r != Role::Wretch)
+        .filter(|r| !r.alignment().is_evil() && *r != Role::Wretch)
         .collect();
 
     let villagers_in_play: Vec<_> = candidate
@@ -374,6 +1679,7 @@ pub fn validate_candidate(
 
     assign_disguises_and_check(
         candidate,
+        deck,
         &wretch_choices,
         &disguise_choices,
         visible_roles,
@@ -386,6 +1692,7 @@ pub fn validate_candidate(
                 full_wretch_assign,
                 full_disguise_assign,
                 observed_statements,
+                topology,
             ) {
                 Ok(_) => {
                     found_valid = true;
@@ -412,13 +1719,14 @@ fn check_statements(
     wretch_assign: &[Role],
     disguise_assign: &[Role],
     observed_statements: &[RoleStatement],
+    topology: Topology,
 ) -> Result<(), Vec<String>> {
     let mut rejection_reasons = Vec::new();
-    let corrupt_permutations = execute_corruption(candidate, wretch_assign);
+    let corrupt_permutations = execute_corruption(candidate, wretch_assign, topology);
 
     'corruption_loop: for pre_corruption in corrupt_permutations {
         let (corruption, uncorruptions) =
-            execute_uncorruption(candidate, disguise_assign, &pre_corruption);
+            execute_uncorruption(candidate, disguise_assign, &pre_corruption, topology);
 
         for (idx, (&true_role, &vis_role, is_corrupt)) in
             izip!(candidate.iter(), disguise_assign.iter(), corruption.iter()).enumerate()
@@ -430,7 +1738,7 @@ fn check_statements(
 
             let lying = true_role.lying() || *is_corrupt;
 
-            let is_valid = can_produce_statement(
+            let is_valid = can_produce_statement_on(
                 vis_role,
                 lying,
                 wretch_assign,
@@ -439,6 +1747,7 @@ fn check_statements(
                 uncorruptions.as_slice(),
                 idx,
                 obs,
+                topology,
             );
 
             if !is_valid {
@@ -469,6 +1778,30 @@ fn check_statements(
     Err(rejection_reasons)
 }
 
+/// Minions a Wretch can register as. Only statements naming a role can tell
+/// minions apart, so every minion nobody names is kept as a single stand-in.
+fn wretch_registrations(deck: &[Role], observed_statements: &[RoleStatement]) -> Vec<Role> {
+    let named: Vec<Role> = observed_statements
+        .iter()
+        .filter_map(RoleStatement::named_role)
+        .collect();
+
+    let mut registrations: Vec<Role> = Vec::new();
+    let mut has_unnamed = false;
+    for &role in deck {
+        if role.group() != Group::Minion || registrations.contains(&role) {
+            continue;
+        }
+        if named.contains(&role) {
+            registrations.push(role);
+        } else if !has_unnamed {
+            has_unnamed = true;
+            registrations.push(role);
+        }
+    }
+    registrations
+}
+
 fn build_choices(
     candidate: &[Role],
     deck_minions: &[Role],
@@ -489,23 +1822,93 @@ fn build_choices(
         });
 
         // Disguise choices
-        let group = r.group();
-        let choices = if group == Group::Demon || r == Role::Drunk || r == Role::Puppet {
-            deck_villager_not_in_play.to_vec()
-        } else if group == Group::Minion {
-            deck_non_evil.to_vec()
-        } else if r == Role::DoppelGanger {
-            villagers_in_play.to_vec()
-        } else {
-            vec![r]
-        };
-
-        disguise_choices.push(choices);
+        disguise_choices.push(
+            disguise_pool(
+                r,
+                deck_non_evil,
+                villagers_in_play,
+                deck_villager_not_in_play,
+            )
+            .map_or_else(|| vec![r], <[Role]>::to_vec),
+        );
     }
 
     (wretch_choices, disguise_choices)
 }
 
+/// Whether finding disguises for a seating can be skipped: with no visible
+/// roles, statements or known corruptions the rules on disguises only look at
+/// which roles are in play, so one set of disguises fits every seating of them
+fn disguises_seating_independent(
+    visible_roles: &[Option<Role>],
+    observed_statements: &[RoleStatement],
+    constraints: &Constraints,
+) -> bool {
+    visible_roles.iter().all(Option::is_none)
+        && observed_statements
+            .iter()
+            .all(|statement| *statement == RoleStatement::NoStatement)
+        && constraints.known_corruptions.iter().all(Option::is_none)
+        && !constraints.uncorrupted.contains(&true)
+}
+
+/// Moves the Wretch replacements and disguises found for `roles` onto the seats
+/// of `seating`, a reordering of the same roles
+fn reseat(
+    roles: &[Role],
+    wretches: &[Role],
+    disguises: &[Role],
+    seating: &[Role],
+) -> (Vec<Role>, Vec<Role>) {
+    let mut taken = vec![false; roles.len()];
+    seating
+        .iter()
+        .map(|role| {
+            let i = (0..roles.len())
+                .find(|&i| !taken[i] && roles[i] == *role)
+                .expect("seating holds the same roles");
+            taken[i] = true;
+            (wretches[i], disguises[i])
+        })
+        .unzip()
+}
+
+/// Whether a seat holding `role` could show up as `shown` with some board
+/// dealt from `deck`
+pub fn can_show_as(deck: &[Role], role: Role, shown: Role) -> bool {
+    let deck_non_evil: Vec<Role> = deck
+        .iter()
+        .copied()
+        .filter(|r| !r.alignment().is_evil() && *r != Role::Wretch)
+        .collect();
+    let deck_villagers: Vec<Role> = deck
+        .iter()
+        .copied()
+        .filter(|r| r.group() == Group::Villager)
+        .collect();
+    disguise_pool(role, &deck_non_evil, &deck_villagers, &deck_villagers)
+        .map_or(role == shown, |pool| pool.contains(&shown))
+}
+
+/// The roles `role` may show instead of itself, `None` if it always shows itself
+fn disguise_pool<'a>(
+    role: Role,
+    deck_non_evil: &'a [Role],
+    villagers_in_play: &'a [Role],
+    deck_villager_not_in_play: &'a [Role],
+) -> Option<&'a [Role]> {
+    let group = role.group();
+    if group == Group::Demon || role == Role::Drunk || role == Role::Puppet {
+        Some(deck_villager_not_in_play)
+    } else if group == Group::Minion {
+        Some(deck_non_evil)
+    } else if role == Role::DoppelGanger {
+        Some(villagers_in_play)
+    } else {
+        None
+    }
+}
+
 fn generate_role_variations(
     v_combo: &[Role],
     o_combo: &[Role],
@@ -520,23 +1923,18 @@ fn generate_role_variations(
     // If there's a counsellor: create all variations where a Villager in v_combo
     // is replaced by an outcast that was not in play.
     if has_counsellor {
-        for (i, &role) in v_combo.iter().enumerate() {
+        for i in 0..v_combo.len() {
             for &outcast in outcasts_not_in_play {
-                // modified v_combo: villager at i becomes the outcast
+                // modified v_combo: villager at i becomes the outcast, the
+                // replaced villager is out of play
                 let mut modified_v_combo = v_combo.to_vec();
-                let original_villager = modified_v_combo[i];
                 modified_v_combo[i] = outcast;
 
-                // modified o_combo: push the original villager into o_combo
-                // (you said v_combo and o_combo must be updated when recursing)
-                let mut modified_o_combo = o_combo.to_vec();
-                modified_o_combo.push(original_villager);
-
                 // Recurse with counsellor flag turned off (so we don't loop infinitely),
                 // but keep has_shaman as-is so shaman replacements can still occur.
                 let mut rec = generate_role_variations(
                     &modified_v_combo,
-                    &modified_o_combo,
+                    o_combo,
                     m_combo,
                     d_combo,
                     outcasts_not_in_play,
@@ -597,12 +1995,7 @@ fn generate_role_combinations(
     outcasts: usize,
     minions: usize,
     demons: usize,
-) -> (
-    Vec<Vec<Role>>,
-    Vec<Vec<Role>>,
-    Vec<Vec<Role>>,
-    Vec<Vec<Role>>,
-) {
+) -> RoleCombinations {
     // Partition deck by group
     let (villager_roles, others): (Vec<Role>, Vec<Role>) = deck
         .iter()
@@ -644,34 +2037,29 @@ fn generate_role_combinations(
     (villager_combos, outcast_combos, minion_combos, demon_combos)
 }
 
-fn permute_multiset<F>(
+/// Calls `process` with every seating of the roles in `counts`. Roles are only
+/// put at seats `can_sit` allows, so hopeless seatings are cut off early.
+#[allow(clippy::too_many_arguments)]
+fn permute_multiset<S, F>(
     counts: &mut HashMap<Role, usize>,
     keys: &[Role],
     current: &mut Vec<Role>,
     target_len: usize,
-    has_counsellor: bool,
     has_puppet: bool,
+    topology: Topology,
+    can_sit: &S,
     process: &mut F,
 ) where
+    S: Fn(usize, Role) -> bool,
     F: FnMut(&[Role]),
 {
     if current.len() == target_len {
         // This can be optimized by checking for it earlier in the run
         let len = current.len();
         if let Some(counsellor_pos) = current.iter().position(|&r| r == Role::Counsellor) {
-            let left_pos = if counsellor_pos == 0 {
-                len - 1
-            } else {
-                counsellor_pos - 1
-            };
-            let right_pos = if counsellor_pos == len - 1 {
-                0
-            } else {
-                counsellor_pos + 1
-            };
-
-            let has_adjacent_outcast = current[left_pos].group() == Group::Outcast
-                || current[right_pos].group() == Group::Outcast;
+            let has_adjacent_outcast = adjacent_seats(len, counsellor_pos, topology)
+                .iter()
+                .any(|&i| current[i].group() == Group::Outcast);
 
             if !has_adjacent_outcast {
                 return;
@@ -684,9 +2072,7 @@ fn permute_multiset<F>(
                 .zip(current.iter().position(|&r| r == Role::Puppeteer))
             {
                 // Puppet must be next to puppeteer
-                if (puppet_pos + 1) % len != puppeteer_pos
-                    && (puppeteer_pos + 1) % len != puppet_pos
-                {
+                if !adjacent_seats(len, puppet_pos, topology).contains(&puppeteer_pos) {
                     return;
                 }
             } else {
@@ -694,20 +2080,9 @@ fn permute_multiset<F>(
                 return;
             }
         } else if let Some(puppeteer_pos) = current.iter().position(|&r| r == Role::Puppeteer) {
-            let len = current.len();
-            let left_pos = if puppeteer_pos == 0 {
-                len - 1
-            } else {
-                puppeteer_pos - 1
-            };
-            let right_pos = if puppeteer_pos == len - 1 {
-                0
-            } else {
-                puppeteer_pos + 1
-            };
-
-            let has_adjacent_villager = current[left_pos].group() == Group::Villager
-                || current[right_pos].group() == Group::Villager;
+            let has_adjacent_villager = adjacent_seats(len, puppeteer_pos, topology)
+                .iter()
+                .any(|&i| current[i].group() == Group::Villager);
 
             // If there's no puppet, then puppeteer can't be next to a villager
             if has_adjacent_villager {
@@ -721,7 +2096,7 @@ fn permute_multiset<F>(
 
     for &k in keys {
         let cnt = counts.get(&k).copied().unwrap_or(0);
-        if cnt == 0 {
+        if cnt == 0 || !can_sit(current.len(), k) {
             continue;
         }
 
@@ -730,13 +2105,7 @@ fn permute_multiset<F>(
         current.push(k);
 
         permute_multiset(
-            counts,
-            keys,
-            current,
-            target_len,
-            has_counsellor,
-            has_puppet,
-            process,
+            counts, keys, current, target_len, has_puppet, topology, can_sit, process,
         );
         // Restore state after exploring this branch
         current.pop();
@@ -752,8 +2121,31 @@ fn confirmed_roles_ok(candidate: &[Role], confirmed_roles: &[Option<Role>]) -> b
         .all(|(r, c)| c.is_none() || c.as_ref() == Some(r))
 }
 
+fn confirmed_alignment_ok(candidate: &[Role], confirmed_alignment: &[Option<Alignment>]) -> bool {
+    candidate
+        .iter()
+        .zip(confirmed_alignment.iter())
+        .all(|(r, a)| a.is_none_or(|a| r.alignment() == a))
+}
+
+fn confirmed_group_ok(candidate: &[Role], confirmed_group: &[Option<Group>]) -> bool {
+    candidate
+        .iter()
+        .zip(confirmed_group.iter())
+        .all(|(r, g)| g.is_none_or(|g| r.group() == g))
+}
+
+fn excluded_roles_ok(candidate: &[Role], excluded_roles: &[Vec<Role>]) -> bool {
+    candidate
+        .iter()
+        .zip(excluded_roles.iter())
+        .all(|(r, excluded)| !excluded.contains(r))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn assign_disguises_and_check<F>(
     candidate: &[Role],
+    deck: &[Role],
     wretch_choices: &[Vec<Role>],
     disguise_choices: &[Vec<Role>],
     visible_roles: &[Option<Role>],
@@ -781,9 +2173,34 @@ where
                 }
             }
 
+            // Evil seats each take their own disguise, two evils never show the same role
+            if candidate[pos].alignment().is_evil()
+                && (0..pos)
+                    .any(|j| candidate[j].alignment().is_evil() && disguise_assign[j] == d_choice)
+            {
+                continue;
+            }
+
+            // Every disguise stands in for a card of the deck, so a role can't be
+            // impersonated more times than the deck has copies of it. Seats really
+            // holding the role aren't counted: a minion may pose as a Confessor next
+            // to every real Confessor in the deck (see
+            // `finds_minion_with_typed_statements`). Checking earlier seats is
+            // enough, each later disguise checks this one in turn.
+            if candidate[pos] != d_choice {
+                let disguised_copies = (0..pos)
+                    .filter(|&j| disguise_assign[j] == d_choice && candidate[j] != d_choice)
+                    .count();
+                let deck_copies = deck.iter().filter(|&&r| r == d_choice).count();
+                if disguised_copies >= deck_copies {
+                    continue;
+                }
+            }
+
             disguise_assign.push(d_choice);
             let success = assign_disguises_and_check(
                 candidate,
+                deck,
                 wretch_choices,
                 disguise_choices,
                 visible_roles,
@@ -801,7 +2218,7 @@ where
 
         wretch_assign.pop();
     }
-    return false;
+    false
 }
 
 fn statements_match(
@@ -809,14 +2226,33 @@ fn statements_match(
     wretch_assign: &[Role],
     disguise_assign: &[Role],
     observed_statements: &[RoleStatement],
-    verbose: bool,
-) -> bool {
+    constraints: &Constraints,
+) -> Result<Vec<bool>, Option<usize>> {
     // NB: This makes us lose corruption data! A proper solution would consider the corruptions separately
-    let corrupt_permutations = execute_corruption(candidate, wretch_assign);
+    let corrupt_permutations = execute_corruption(candidate, wretch_assign, constraints.topology);
+    // The failing seat of the corruption permutation that got the furthest
+    let mut furthest_failure: Option<usize> = None;
 
     'corruption_loop: for pre_corruption in corrupt_permutations {
-        let (corruption, uncorruptions) =
-            execute_uncorruption(candidate, disguise_assign, &pre_corruption);
+        let (corruption, uncorruptions) = execute_uncorruption(
+            candidate,
+            disguise_assign,
+            &pre_corruption,
+            constraints.topology,
+        );
+
+        // Skip corruption spreads the player already knows didn't happen
+        if corruption
+            .iter()
+            .zip(&constraints.known_corruptions)
+            .any(|(corrupt, known)| known.is_some_and(|known| known != *corrupt))
+            || corruption
+                .iter()
+                .zip(&constraints.uncorrupted)
+                .any(|(corrupt, uncorrupted)| *corrupt && *uncorrupted)
+        {
+            continue;
+        }
 
         for (idx, (&true_role, &vis_role, is_corrupt)) in
             izip!(candidate.iter(), disguise_assign.iter(), corruption.iter()).enumerate()
@@ -826,9 +2262,11 @@ fn statements_match(
                 continue;
             }
 
-            let lying = true_role.lying() || *is_corrupt;
+            let lying = true_role.lying()
+                || *is_corrupt
+                || constraints.force_lying.get(idx).copied().unwrap_or(false);
 
-            let is_valid = can_produce_statement(
+            let is_valid = can_produce_statement_on(
                 vis_role,
                 lying,
                 wretch_assign,
@@ -837,25 +2275,20 @@ fn statements_match(
                 uncorruptions.as_slice(),
                 idx,
                 obs,
+                constraints.topology,
             );
 
             // If not valid, reject candidate
             if !is_valid {
-                if verbose {
-                    let candidate_str = candidate
-                        .iter()
-                        .zip(corruption.iter())
-                        .map(|(role, corrupted)| {
-                            if *corrupted {
-                                format!("{}*", role)
-                            } else {
-                                role.to_string()
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ");
-
-                    eprintln!(
+                furthest_failure = furthest_failure.max(Some(idx));
+                if log::log_enabled!(log::Level::Trace) {
+                    let candidate_str = format_solution_detailed(&Solution {
+                        true_roles: candidate.to_vec(),
+                        disguised_roles: disguise_assign.to_vec(),
+                        corrupted: corruption.clone(),
+                    });
+
+                    log::trace!(
                         "Invalid candidate: [{}]\nStatement {} didn't match for role {} (visible as {}, lying: {})",
                         candidate_str,
                         obs, true_role, vis_role, lying
@@ -865,13 +2298,17 @@ fn statements_match(
             }
         }
         // All statements matched
-        return true;
+        return Ok(corruption);
     }
     // All corruption permutationed had some statement that didn't match
-    return false;
+    Err(furthest_failure)
 }
 
-fn execute_corruption(true_roles: &[Role], wretch_assign: &[Role]) -> Vec<Vec<bool>> {
+fn execute_corruption(
+    true_roles: &[Role],
+    wretch_assign: &[Role],
+    topology: Topology,
+) -> Vec<Vec<bool>> {
     let len = true_roles.len();
     let mut poison_options: Vec<Vec<usize>> = Vec::new();
 
@@ -879,6 +2316,7 @@ fn execute_corruption(true_roles: &[Role], wretch_assign: &[Role]) -> Vec<Vec<bo
     let mut roles_with_indices: Vec<(usize, Role)> = true_roles
         .iter()
         .enumerate()
+        .filter(|(_, r)| r.is_corruption_source())
         .map(|(i, &r)| (i, r))
         .collect();
     roles_with_indices.sort_by(|a, b| {
@@ -894,7 +2332,8 @@ fn execute_corruption(true_roles: &[Role], wretch_assign: &[Role]) -> Vec<Vec<bo
             .then_with(|| a.0.cmp(&b.0))
     });
 
-    // Collect lists of lists to permute over
+    // Collect lists of lists to permute over. Only villagers get corrupted, an
+    // outcast or evil seat next to a Pooka or Poisoner is left alone.
     for (i, role) in roles_with_indices {
         match role {
             Role::Drunk => {
@@ -903,7 +2342,7 @@ fn execute_corruption(true_roles: &[Role], wretch_assign: &[Role]) -> Vec<Vec<bo
             }
             Role::Pooka => {
                 // All neighbouring villagers
-                let neighbors = neighbor_indexes(len, i, 1);
+                let neighbors = adjacent_seats(len, i, topology);
                 for n in neighbors {
                     if wretch_assign[n].group() == Group::Villager {
                         poison_options.push(vec![n]); // We pretend it's actually two separate roles making choices
@@ -912,7 +2351,7 @@ fn execute_corruption(true_roles: &[Role], wretch_assign: &[Role]) -> Vec<Vec<bo
             }
             Role::Poisoner => {
                 // One neighbouring villager
-                let neighbors = neighbor_indexes(len, i, 1);
+                let neighbors = adjacent_seats(len, i, topology);
                 let eligible: Vec<usize> = neighbors
                     .into_iter()
                     .filter(|&n| wretch_assign[n].group() == Group::Villager)
@@ -970,29 +2409,37 @@ fn execute_corruption(true_roles: &[Role], wretch_assign: &[Role]) -> Vec<Vec<bo
     result
 }
 
+/// Alchemists all resolve against the corruption as it was before anyone cured:
+/// a corrupted Alchemist cures nothing (even if another Alchemist cures it), and
+/// a seat in range of several Alchemists counts towards each of their totals.
 fn execute_uncorruption(
     true_roles: &[Role],
     disguised_roles: &[Role],
     corruption: &[bool],
+    topology: Topology,
 ) -> (Vec<bool>, Vec<usize>) {
     let len = corruption.len();
     let mut mut_corruption = corruption.to_vec();
     let mut cleared_counts = vec![0_usize; len];
 
     for i in 0..len {
-        if disguised_roles[i] == Role::Alchemist && !mut_corruption[i] && !true_roles[i].lying() {
-            let mut cleared = 0;
-
-            for &offset in &[1, 2] {
-                for &neighbor in &neighbor_indexes(len, i, offset) {
-                    if mut_corruption[neighbor] && true_roles[neighbor] != Role::Drunk {
+        let curer = disguised_roles[i];
+        if curer.is_uncorruption_source() && !corruption[i] && !true_roles[i].lying() {
+            let mut cleared: Vec<usize> = Vec::new();
+
+            for &offset in curer.cure_offsets() {
+                for &neighbor in &neighbor_indexes_on(len, i, offset, topology) {
+                    if corruption[neighbor]
+                        && true_roles[neighbor] != Role::Drunk
+                        && !cleared.contains(&neighbor)
+                    {
                         mut_corruption[neighbor] = false;
-                        cleared += 1;
+                        cleared.push(neighbor);
                     }
                 }
             }
 
-            cleared_counts[i] = cleared;
+            cleared_counts[i] = cleared.len();
         }
     }
 
@@ -0,0 +1,34 @@
+use demon_deduce::{DeckInfo, Role};
+
+#[test]
+fn test_deck_info_counts() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Hunter, Wretch, Minion, Baa];
+    let info = DeckInfo::from(deck.as_slice());
+
+    assert_eq!(info.villager_count(), 3);
+    assert_eq!(info.outcast_count(), 1);
+    assert_eq!(info.minion_count(), 1);
+    assert_eq!(info.demon_count(), 1);
+
+    let mut distributions = info.valid_distributions(5);
+    distributions.sort();
+    assert_eq!(
+        distributions,
+        vec![(2, 1, 1, 1), (3, 0, 1, 1), (3, 1, 0, 1), (3, 1, 1, 0)]
+    );
+    assert!(info.valid_distributions(7).is_empty());
+}
+
+#[test]
+fn test_deck_info_counsellor() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Hunter, Wretch, Counsellor];
+    let info = DeckInfo::from(deck.as_slice());
+
+    // With the Counsellor in play the Wretch has to stay in the deck for it
+    let distributions = info.valid_distributions(4);
+    assert!(distributions.contains(&(3, 0, 1, 0)));
+    assert!(!distributions.contains(&(2, 1, 1, 0)));
+    assert!(distributions.contains(&(3, 1, 0, 0)));
+}
@@ -0,0 +1,50 @@
+use demon_deduce::roles::*;
+use demon_deduce::{brute_force_solve, Role};
+use log::{LevelFilter, Metadata, Record};
+use std::sync::Mutex;
+
+/// Keeps every message so the test can look at them
+struct CaptureLogger(Mutex<Vec<String>>);
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+#[test]
+fn test_invalid_candidates_are_traced() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    use Role::*;
+    let deck = vec![Confessor, Lover, Minion];
+    let visible = vec![Some(Confessor), Some(Lover), None];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![
+        ConfessorStatement::IAmDizzy.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    // Only a lying Confessor says it's dizzy, so the truthful boards get rejected
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert!(!solutions.is_empty());
+
+    let messages = LOGGER.0.lock().unwrap();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.starts_with("Invalid candidate: [Confessor, Lover, Minion(as Confessor)]")),
+        "{:#?}",
+        *messages
+    );
+}
@@ -0,0 +1,72 @@
+use demon_deduce::roles::*;
+use demon_deduce::{render_board, render_board_numbered, Coloring, Role};
+
+#[test]
+fn test_render_board() {
+    use Role::*;
+    let board = vec![Confessor, Minion, FortuneTeller];
+    let visible = vec![Some(Confessor), None, Some(Lover)];
+    let confirmed = vec![None, None, Some(FortuneTeller)];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        LoverStatement { evil_count: 1 }.into(),
+    ];
+
+    assert_eq!(
+        render_board(&board, &visible, &confirmed, &observed, Coloring::None),
+        "Player 0: Confessor - I am Good => Confessor\n\
+         Player 1: Unrevealed - No Statement => Minion\n\
+         Player 2: Lover (Fortune Teller) - There are 1 Evil adjacent to me => Fortune Teller\n"
+    );
+
+    assert_eq!(
+        render_board(
+            &[],
+            &visible[..1],
+            &confirmed[..1],
+            &observed[..1],
+            Coloring::None
+        ),
+        "Player 0: Confessor - I am Good\n"
+    );
+
+    // Shown and confirmed roles go by their in-game names too
+    assert_eq!(
+        render_board(
+            &[],
+            &[Some(PlagueDoctor)],
+            &[Some(TwinMinion)],
+            &[RoleStatement::NoStatement],
+            Coloring::None
+        ),
+        "Player 0: Plague Doctor (Twin Minion) - No Statement\n"
+    );
+}
+
+#[test]
+fn test_render_sixteen_seats_from_one() {
+    use Role::*;
+    let mut visible = vec![None; 16];
+    visible[0] = Some(Empress);
+    visible[1] = Some(Gemcrafter);
+    let confirmed = vec![None; 16];
+    let mut observed = vec![RoleStatement::NoStatement; 16];
+    observed[0] = EmpressStatement {
+        target_indexes: to_bitvec(vec![2, 15]),
+    }
+    .into();
+    observed[1] = GemcrafterStatement { target_index: 15 }.into();
+
+    let rendered = render_board_numbered(1, &[], &visible, &confirmed, &observed, Coloring::None);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 16);
+    // The last seat doesn't fit once moved up, so that statement stays as it is
+    assert!(lines[0].ends_with("(seats from 0)"), "{}", rendered);
+    assert!(lines[1].contains("#16 is good"), "{}", rendered);
+    assert!(
+        lines[15].starts_with("Player 16: Unrevealed"),
+        "{}",
+        rendered
+    );
+}
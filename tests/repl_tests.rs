@@ -0,0 +1,133 @@
+use demon_deduce::run_repl_with;
+
+fn run(script: &str) -> String {
+    let mut out = Vec::new();
+    run_repl_with(script.as_bytes(), &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+/// The per-seat lines printed after each command that changed the puzzle
+fn reports(output: &str) -> Vec<Vec<&str>> {
+    output
+        .split("Found ")
+        .skip(1)
+        .map(|report| {
+            report
+                .lines()
+                .skip(1)
+                .take_while(|l| l.starts_with(|c: char| c.is_ascii_digit()))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_repl_narrows() {
+    let output = run("load confessor,confessor,minion 2 0 1 0 ? ? ?
+reveal 1 confessor
+confirm 2 confessor
+claim 1 iamdizzy
+quit
+reveal 3 confessor
+");
+    assert_eq!(
+        reports(&output),
+        vec![
+            vec![
+                "1: Confessor, Minion",
+                "2: Confessor, Minion",
+                "3: Confessor, Minion"
+            ],
+            vec![
+                "1: Confessor, Minion",
+                "2: Confessor, Minion",
+                "3: Confessor, Minion"
+            ],
+            vec![
+                "1: Confessor, Minion",
+                "2: Confessor",
+                "3: Confessor, Minion"
+            ],
+            vec!["1: Minion", "2: Confessor", "3: Confessor"],
+        ],
+        "{}",
+        output
+    );
+    assert!(output.contains("Found 1 solution(s)"), "{}", output);
+}
+
+#[test]
+fn test_repl_errors() {
+    let output = run("reveal 1 confessor
+load confessor,minion 1 0 1 0 ? ?
+confirm 2 hunter
+reveal 5 confessor
+claim 2 iamgood
+dance
+");
+    assert!(output.contains("Load a puzzle first"), "{}", output);
+    assert!(output.contains("Hunter is not in deck"), "{}", output);
+    assert!(
+        output.contains("Invalid seat '5' (must be 1-2)"),
+        "{}",
+        output
+    );
+    assert!(output.contains("Seat 2 is unrevealed"), "{}", output);
+    assert!(output.contains("Unknown command 'dance'"), "{}", output);
+}
+
+#[test]
+fn test_repl_changed_confirmation_solves_again() {
+    let output = run(
+        "load confessor,confessor,minion 2 0 1 0 confessor::iamgood confessor::iamgood confessor::iamdizzy
+confirm 3 confessor
+confirm 3 minion
+",
+    );
+    assert_eq!(
+        reports(&output),
+        vec![
+            vec!["1: Confessor", "2: Confessor", "3: Minion"],
+            vec!["1: Confessor", "2: Confessor", "3: Minion"],
+        ],
+        "{}",
+        output
+    );
+    assert_eq!(
+        output.matches("No solutions found.").count(),
+        1,
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_repl_numbers_seats_from_one() {
+    let output = run("load gemcrafter,confessor,minion 2 0 1 0 ? ? ?
+reveal 1 gemcrafter
+claim 1 2
+show
+");
+    assert!(
+        output.contains("Player 1: Gemcrafter - #2 is good"),
+        "{}",
+        output
+    );
+    assert!(output.contains("Player 3: Unrevealed"), "{}", output);
+    assert!(!output.contains("Player 0"), "{}", output);
+}
+
+#[test]
+fn test_repl_reports_invalid_puzzles() {
+    let output = run("load confessor,hunter,minion 2 0 1 0 ? ? ?
+reveal 1 confessor
+confirm 1 hunter
+");
+    assert!(
+        output.contains(
+            "Invalid puzzle: Seat 1 is confirmed as Hunter but shows Confessor, which it can't disguise as"
+        ),
+        "{}",
+        output
+    );
+}
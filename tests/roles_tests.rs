@@ -0,0 +1,1276 @@
+use demon_deduce::roles::*;
+use demon_deduce::Role;
+
+#[test]
+fn test_display_names() {
+    assert_eq!(Role::FortuneTeller.display_name(), "Fortune Teller");
+    assert_eq!(Role::DoppelGanger.display_name(), "Doppelganger");
+    assert_eq!(Role::PlagueDoctor.display_name(), "Plague Doctor");
+    assert_eq!(Role::TwinMinion.display_name(), "Twin Minion");
+    assert_eq!(Role::Baa.display_name(), "Baa");
+    assert_eq!(Role::Confessor.display_name(), "Confessor");
+}
+
+#[test]
+fn test_board_positions() {
+    use Role::*;
+    let board = vec![Confessor, Minion, Wretch, Baa, Witch];
+
+    assert_eq!(evil_positions(&board), vec![1, 3, 4]);
+    assert_eq!(minion_positions(&board), vec![1, 4]);
+    assert_eq!(demon_position(&board), Some(3));
+    assert_eq!(demon_position(&[Confessor, Minion]), None);
+}
+
+#[test]
+fn test_oracle_three_targets() {
+    use Role::*;
+    let statement: RoleStatement = Oracle
+        .parse_natural_statement("#2, #3 or #4 is a Witch")
+        .unwrap();
+    assert_eq!(
+        statement,
+        OracleStatement {
+            target_indexes: to_bitvec(vec![1, 2, 3]),
+            claim: OracleClaim::Role(Witch),
+        }
+        .into()
+    );
+
+    assert_eq!(
+        Oracle
+            .parse_natural_statement("#1 or #4 is a Witch")
+            .unwrap(),
+        OracleStatement {
+            target_indexes: to_bitvec(vec![0, 3]),
+            claim: OracleClaim::Role(Witch),
+        }
+        .into()
+    );
+
+    let board = vec![Oracle, Confessor, Lover, Witch];
+    let corruptions = vec![false; board.len()];
+    let uncorruptions = vec![0; board.len()];
+    let check = |board: &[Role]| {
+        can_produce_statement(
+            Oracle,
+            false,
+            board,
+            board,
+            &corruptions,
+            &uncorruptions,
+            0,
+            &statement,
+        )
+    };
+    assert!(check(&board));
+    assert!(!check(&[Oracle, Confessor, Lover, Minion]));
+
+    assert!(Oracle.parse_statement("1;witch").is_err());
+    let lone_target: RoleStatement = OracleStatement {
+        target_indexes: to_bitvec(vec![3]),
+        claim: OracleClaim::Role(Witch),
+    }
+    .into();
+    assert!(!can_produce_statement(
+        Oracle,
+        false,
+        &board,
+        &board,
+        &corruptions,
+        &uncorruptions,
+        0,
+        &lone_target,
+    ));
+}
+
+#[test]
+fn test_out_of_range_targets_are_rejected() {
+    use Role::*;
+    let board = vec![Empress, Judge, Confessor, Minion];
+    let corruptions = vec![false; board.len()];
+    let uncorruptions = vec![0; board.len()];
+
+    let statements: Vec<(Role, RoleStatement)> = vec![
+        (
+            Empress,
+            EmpressStatement {
+                target_indexes: to_bitvec(vec![1, 2, 4]),
+            }
+            .into(),
+        ),
+        (
+            Judge,
+            JudgeStatement {
+                target_index: 4,
+                is_lying: true,
+            }
+            .into(),
+        ),
+        (
+            Dreamer,
+            DreamerStatement {
+                target_index: 4,
+                role: Minion,
+                negated: false,
+            }
+            .into(),
+        ),
+        (
+            Medium,
+            MediumStatement {
+                target_index: 4,
+                role: Confessor,
+            }
+            .into(),
+        ),
+        (
+            PlagueDoctor,
+            PlagueDoctorStatement {
+                corruption_index: 4,
+                evil_index: None,
+            }
+            .into(),
+        ),
+    ];
+
+    for (role, statement) in &statements {
+        for lying in [false, true] {
+            assert!(
+                !can_produce_statement(
+                    *role,
+                    lying,
+                    &board,
+                    &board,
+                    &corruptions,
+                    &uncorruptions,
+                    0,
+                    statement,
+                ),
+                "{:?} accepted {}",
+                role,
+                statement
+            );
+        }
+    }
+
+    assert!(Empress.parse_statement("1,2,99").is_err());
+}
+
+#[test]
+fn test_statements_naming_the_speaker() {
+    use Role::*;
+    let board = vec![Bishop, Empress, Jester, Minion, Lover];
+    let corruptions = vec![false; board.len()];
+    let uncorruptions = vec![0; board.len()];
+    let produces = |position: usize, lying: bool, statement: &RoleStatement| {
+        can_produce_statement(
+            board[position],
+            lying,
+            &board,
+            &board,
+            &corruptions,
+            &uncorruptions,
+            position,
+            statement,
+        )
+    };
+
+    // The Bishop and Empress are only shown other seats
+    let bishop: RoleStatement = BishopStatement {
+        target_indexes: to_bitvec(vec![0, 3, 4]),
+    }
+    .into();
+    let empress: RoleStatement = EmpressStatement {
+        target_indexes: to_bitvec(vec![1, 3, 4]),
+    }
+    .into();
+    for lying in [false, true] {
+        assert!(!produces(0, lying, &bishop));
+        assert!(!produces(1, lying, &empress));
+    }
+    assert!(names_speaker(&bishop, 0));
+    assert!(names_speaker(&empress, 1));
+    assert!(!names_speaker(&empress, 0));
+
+    // Without the speaker the same Empress statement is fine
+    let empress: RoleStatement = EmpressStatement {
+        target_indexes: to_bitvec(vec![2, 3, 4]),
+    }
+    .into();
+    assert!(produces(1, false, &empress));
+
+    // The Jester picks its seats, itself included, and counts itself as good
+    let jester: RoleStatement = JesterStatement {
+        target_indexes: to_bitvec(vec![2, 3, 4]),
+        evil_count: 1,
+    }
+    .into();
+    assert!(!names_speaker(&jester, 2));
+    assert!(produces(2, false, &jester));
+    assert!(!produces(2, true, &jester));
+}
+
+#[test]
+fn test_gemcrafter_naming_itself() {
+    use Role::*;
+    let board = vec![Gemcrafter, Minion, Confessor, Lover];
+    let corruptions = vec![true, false, false, false];
+    let uncorruptions = vec![0; board.len()];
+    let gemcrafter_says = |true_roles: &[Role], lying: bool, target_index: usize| {
+        can_produce_statement(
+            Gemcrafter,
+            lying,
+            true_roles,
+            &board,
+            &corruptions,
+            &uncorruptions,
+            0,
+            &GemcrafterStatement { target_index }.into(),
+        )
+    };
+
+    // A corrupted Gemcrafter points at an evil seat, but never at itself
+    assert!(gemcrafter_says(&board, true, 1));
+    assert!(!gemcrafter_says(&board, true, 0));
+    assert!(!gemcrafter_says(&board, false, 0));
+
+    // Nor does a Minion showing as one, even though that would be a lie
+    let disguised = vec![Minion, Poisoner, Confessor, Lover];
+    assert!(!gemcrafter_says(&disguised, true, 0));
+    assert!(names_speaker(
+        &GemcrafterStatement { target_index: 0 }.into(),
+        0
+    ));
+    assert!(!names_speaker(
+        &GemcrafterStatement { target_index: 2 }.into(),
+        0
+    ));
+}
+
+#[test]
+fn test_possible_statements_are_accepted() {
+    use Role::*;
+    let board = vec![Hunter, Minion, Enlightened, Architect, Baa, Lover, Judge];
+    let disguised = vec![Hunter, Lover, Enlightened, Architect, Knitter, Lover, Judge];
+    let corruptions = vec![false; board.len()];
+    let uncorruptions = vec![0; board.len()];
+
+    for position in [0, 2, 3, 5, 6] {
+        let statements =
+            possible_statements(board[position], &board, &disguised, &corruptions, position);
+        assert!(
+            !statements.is_empty(),
+            "No statements for {:?}",
+            board[position]
+        );
+        for statement in &statements {
+            assert!(
+                can_produce_statement(
+                    board[position],
+                    false,
+                    &board,
+                    &disguised,
+                    &corruptions,
+                    &uncorruptions,
+                    position,
+                    statement,
+                ),
+                "{:?} rejected its own statement {}",
+                board[position],
+                statement
+            );
+        }
+    }
+
+    assert_eq!(
+        possible_statements(Hunter, &board, &disguised, &corruptions, 0),
+        vec![HunterStatement { distance: 1 }.into()]
+    );
+    assert_eq!(
+        possible_statements(Judge, &board, &disguised, &corruptions, 6).len(),
+        board.len() - 1
+    );
+    assert!(possible_statements(Empress, &board, &disguised, &corruptions, 0).is_empty());
+}
+
+#[test]
+fn test_adjacent_seats() {
+    assert_eq!(adjacent_seats(2, 0, Topology::Circle), vec![1]);
+    assert_eq!(adjacent_seats(2, 1, Topology::Circle), vec![0]);
+
+    assert_eq!(adjacent_seats(3, 0, Topology::Circle), vec![2, 1]);
+    assert_eq!(adjacent_seats(3, 1, Topology::Circle), vec![0, 2]);
+
+    assert_eq!(adjacent_seats(7, 0, Topology::Circle), vec![6, 1]);
+    assert_eq!(adjacent_seats(7, 3, Topology::Circle), vec![2, 4]);
+    assert_eq!(adjacent_seats(7, 6, Topology::Circle), vec![5, 0]);
+    assert_eq!(adjacent_seats(7, 0, Topology::Line), vec![1]);
+    assert_eq!(adjacent_seats(7, 6, Topology::Line), vec![5]);
+}
+
+#[test]
+fn test_lover_on_small_boards() {
+    use Role::*;
+    let lover_says = |board: &[Role], lying: bool, evil_count: usize| {
+        can_produce_statement(
+            Lover,
+            lying,
+            board,
+            board,
+            &vec![false; board.len()],
+            &vec![0; board.len()],
+            0,
+            &LoverStatement { evil_count }.into(),
+        )
+    };
+
+    // With two seats the only neighbour isn't counted twice
+    let board = [Lover, Minion];
+    assert_eq!(neighbor_indexes(2, 0, 1), vec![1]);
+    assert!(lover_says(&board, false, 1));
+    assert!(!lover_says(&board, false, 2));
+    assert!(lover_says(&board, true, 0));
+    assert!(!lover_says(&board, true, 2));
+
+    // With three both neighbours count, a lie still can't go past them
+    let board = [Lover, Minion, Poisoner];
+    assert_eq!(neighbor_indexes(3, 0, 1), vec![2, 1]);
+    assert!(lover_says(&board, false, 2));
+    assert!(lover_says(&board, true, 0));
+    assert!(lover_says(&board, true, 1));
+    assert!(!lover_says(&board, true, 3));
+
+    assert!(neighbor_indexes(1, 0, 1).is_empty());
+}
+
+#[test]
+fn test_architect_leaves_out_its_own_seat() {
+    use Role::*;
+    let architect_says = |board: &[Role], position: usize, lying: bool, statement| {
+        can_produce_statement(
+            Architect,
+            lying,
+            board,
+            board,
+            &vec![false; board.len()],
+            &vec![0; board.len()],
+            position,
+            &RoleStatement::Architect(statement),
+        )
+    };
+
+    // From seat 0 the right side is seats 1 and 2, the left side seats 3 and
+    // 4. Counting the Minion speaking from seat 0 would even the sides out.
+    let board = [Minion, Confessor, Lover, Knight, Poisoner];
+    assert!(architect_says(&board, 0, true, ArchitectStatement::Equal));
+    assert!(architect_says(&board, 0, true, ArchitectStatement::Right));
+    assert!(!architect_says(&board, 0, true, ArchitectStatement::Left));
+    assert!(architect_says(&board, 0, false, ArchitectStatement::Left));
+    assert_eq!(
+        possible_statements(Architect, &board, &board, &[false; 5], 0),
+        vec![ArchitectStatement::Left.into()]
+    );
+
+    // The sides are the seats beside the Architect, wherever it sits
+    let board = [Confessor, Knight, Architect, Poisoner, Lover];
+    assert!(architect_says(&board, 2, false, ArchitectStatement::Right));
+    let board = [Poisoner, Knight, Architect, Confessor, Lover];
+    assert!(architect_says(&board, 2, false, ArchitectStatement::Left));
+
+    // On even boards the seat straight across is on neither side
+    let board = [Architect, Confessor, Lover, Poisoner, Knight, Bard];
+    assert!(architect_says(&board, 0, false, ArchitectStatement::Equal));
+}
+
+#[test]
+fn test_closest_evil_direction_single_evil() {
+    use Role::*;
+    for len in 3..=8 {
+        for evil in 1..len {
+            let mut board = vec![Confessor; len];
+            board[evil] = Minion;
+
+            let clockwise = evil;
+            let counter_clockwise = len - evil;
+            let expected = match clockwise.cmp(&counter_clockwise) {
+                std::cmp::Ordering::Less => EnlightenedStatement::Clockwise,
+                std::cmp::Ordering::Greater => EnlightenedStatement::CounterClockwise,
+                std::cmp::Ordering::Equal => EnlightenedStatement::Equidistant,
+            };
+            assert_eq!(
+                closest_evil_direction(&board, 0),
+                expected,
+                "len {} with evil at {}",
+                len,
+                evil
+            );
+        }
+    }
+
+    // A closer evil wins over one straight across
+    let board = vec![Confessor, Confessor, Minion, Minion, Confessor, Confessor];
+    assert_eq!(
+        closest_evil_direction(&board, 0),
+        EnlightenedStatement::Clockwise
+    );
+    let board = vec![Confessor, Minion, Confessor, Confessor, Confessor, Minion];
+    assert_eq!(
+        closest_evil_direction(&board, 0),
+        EnlightenedStatement::Equidistant
+    );
+}
+
+#[test]
+fn test_max_ring_distance() {
+    use Role::*;
+    for (len, expected) in [(4, 2), (5, 2), (6, 3), (7, 3)] {
+        assert_eq!(max_ring_distance(len), expected, "len {}", len);
+
+        // The farthest seat clockwise is still found by both helpers
+        let mut board = vec![Confessor; len];
+        board[expected] = Minion;
+        assert_eq!(
+            closest_evil_distance(&board, 0),
+            Some(expected),
+            "len {}",
+            len
+        );
+
+        let corruptions: Vec<bool> = (0..len).map(|i| i == expected).collect();
+        assert_eq!(
+            closest_corrupt_distance(&corruptions, 0),
+            Some(expected),
+            "len {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn test_line_topology_has_no_wraparound() {
+    use Role::*;
+    let board = vec![Hunter, Confessor, Confessor, Confessor, Confessor, Minion];
+
+    // Around a circle the last seat is next to the first, in a line it is five away
+    assert_eq!(neighbor_indexes(6, 0, 1), vec![5, 1]);
+    assert_eq!(neighbor_indexes_on(6, 0, 1, Topology::Line), vec![1]);
+    assert_eq!(closest_evil_distance(&board, 0), Some(1));
+    assert_eq!(closest_evil_distance_on(&board, 0, Topology::Line), Some(5));
+    assert_eq!(
+        closest_evil_direction(&board, 0),
+        EnlightenedStatement::CounterClockwise
+    );
+    assert_eq!(
+        closest_evil_direction_on(&board, 0, Topology::Line),
+        EnlightenedStatement::Clockwise
+    );
+
+    for (topology, distance) in [(Topology::Circle, 1), (Topology::Line, 5)] {
+        let statement = HunterStatement { distance }.into();
+        assert!(
+            can_produce_statement_on(
+                Hunter,
+                false,
+                &board,
+                &board,
+                &[false; 6],
+                &[0; 6],
+                0,
+                &statement,
+                topology,
+            ),
+            "{:?}",
+            topology
+        );
+        assert_eq!(
+            possible_statements_on(Hunter, &board, &board, &[false; 6], 0, topology),
+            vec![statement]
+        );
+    }
+    assert_eq!(
+        possible_statements(Hunter, &board, &board, &[false; 6], 0),
+        vec![HunterStatement { distance: 1 }.into()]
+    );
+}
+
+#[test]
+fn test_scout_named_role_with_disguised_copy() {
+    use Role::*;
+    let check = |true_roles: &[Role], disguised: &[Role], lying: bool, distance: usize| {
+        can_produce_statement(
+            Scout,
+            lying,
+            true_roles,
+            disguised,
+            &vec![false; true_roles.len()],
+            &vec![0; true_roles.len()],
+            0,
+            &ScoutStatement {
+                role: Some(Witch),
+                distance,
+            }
+            .into(),
+        )
+    };
+
+    // Two real Witches at distance 1 and 3, plus a Minion showing as a Witch
+    let board = vec![Scout, Witch, Minion, Confessor, Confessor, Witch, Confessor];
+    let disguised = vec![Scout, Witch, Witch, Confessor, Confessor, Witch, Confessor];
+    for (distance, truthful) in [(1, true), (2, false), (3, true)] {
+        assert_eq!(check(&board, &disguised, false, distance), truthful);
+        assert_eq!(check(&board, &disguised, true, distance), !truthful);
+    }
+
+    // Only the real Witch counts, not the Minion shown as one next to the Baa
+    let board = vec![
+        Scout, Minion, Baa, Confessor, Confessor, Confessor, Witch, Confessor,
+    ];
+    let disguised = vec![
+        Scout, Witch, Lover, Confessor, Confessor, Confessor, Witch, Confessor,
+    ];
+    for (distance, truthful) in [(1, false), (3, true)] {
+        assert_eq!(check(&board, &disguised, false, distance), truthful);
+        assert_eq!(check(&board, &disguised, true, distance), !truthful);
+    }
+}
+
+#[test]
+fn test_empress_any_target_count() {
+    use Role::*;
+    let board = vec![Empress, Confessor, Minion, Lover, Baa, Knight];
+    let check = |targets: Vec<usize>, lying: bool| {
+        can_produce_statement(
+            Empress,
+            lying,
+            &board,
+            &board,
+            &[false; 6],
+            &[0; 6],
+            0,
+            &EmpressStatement {
+                target_indexes: to_bitvec(targets),
+            }
+            .into(),
+        )
+    };
+
+    assert!(check(vec![1, 2], false));
+    assert!(!check(vec![1, 3], false));
+    assert!(!check(vec![2, 4], false));
+    assert!(check(vec![1, 2, 3, 5], false));
+    assert!(!check(vec![1, 2, 4, 5], false));
+    assert!(check(vec![1, 3, 5], true));
+
+    assert_eq!(
+        Empress
+            .parse_natural_statement("One is Evil: #2, #3, #4 or #6")
+            .unwrap(),
+        EmpressStatement {
+            target_indexes: to_bitvec(vec![1, 2, 3, 5]),
+        }
+        .into()
+    );
+    assert_eq!(
+        Empress
+            .parse_natural_statement("One is Evil: #2 or #3")
+            .unwrap(),
+        EmpressStatement {
+            target_indexes: to_bitvec(vec![1, 2]),
+        }
+        .into()
+    );
+}
+
+#[test]
+fn test_named_role() {
+    use Role::*;
+    assert_eq!(
+        RoleStatement::from(ScoutStatement {
+            role: Some(Witch),
+            distance: 2,
+        })
+        .named_role(),
+        Some(Witch)
+    );
+    assert_eq!(
+        RoleStatement::from(DruidStatement {
+            target_indexes: to_bitvec(vec![1, 2]),
+            role: None,
+        })
+        .named_role(),
+        None
+    );
+    assert_eq!(
+        RoleStatement::from(LoverStatement { evil_count: 1 }).named_role(),
+        None
+    );
+}
+
+#[test]
+fn test_oracle_group() {
+    use Role::*;
+    let statement = Oracle
+        .parse_natural_statement("#2 or #4 is an Outcast")
+        .unwrap();
+    assert_eq!(
+        statement,
+        OracleStatement {
+            target_indexes: to_bitvec(vec![1, 3]),
+            claim: OracleClaim::Group(Group::Outcast),
+        }
+        .into()
+    );
+    assert_eq!(
+        Oracle.parse_statement("1,3;outcasts"),
+        Ok(statement.clone())
+    );
+    // A name that's both a role and a group means the role
+    assert_eq!(
+        Oracle.parse_statement("1,3;minion"),
+        Ok(OracleStatement {
+            target_indexes: to_bitvec(vec![1, 3]),
+            claim: OracleClaim::Role(Minion),
+        }
+        .into())
+    );
+
+    let corruptions = vec![false; 4];
+    let uncorruptions = vec![0; 4];
+    let check = |board: &[Role], is_lying| {
+        can_produce_statement(
+            Oracle,
+            is_lying,
+            board,
+            board,
+            &corruptions,
+            &uncorruptions,
+            0,
+            &statement,
+        )
+    };
+    let with_outcast = [Oracle, Bombardier, Minion, Knight];
+    assert!(check(&with_outcast, false));
+    // Lying about a group works like lying about a role, no named seat is evil
+    assert!(check(&with_outcast, true));
+    // An outcast next to an evil seat isn't enough, the other one has to be good
+    let next_to_evil = [Oracle, Bombardier, Knight, Minion];
+    assert!(!check(&next_to_evil, false));
+    assert!(!check(&next_to_evil, true));
+    let both_evil = [Oracle, Minion, Knight, Poisoner];
+    assert!(!check(&both_evil, false));
+    assert!(!check(&both_evil, true));
+
+    // Any minion needs the group spelled out
+    assert_eq!(
+        Oracle.parse_statement("1,3;group:minion"),
+        Ok(OracleStatement {
+            target_indexes: to_bitvec(vec![1, 3]),
+            claim: OracleClaim::Group(Group::Minion),
+        }
+        .into())
+    );
+    assert!(Oracle.parse_statement("1,3;group:confessor").is_err());
+}
+
+#[test]
+fn test_hunter_without_evils() {
+    use Role::*;
+    let board = vec![Hunter, Confessor, Lover, Knight];
+    assert_eq!(closest_evil_distance(&board, 0), None);
+    assert!(possible_statements(Hunter, &board, &board, &[false; 4], 0).is_empty());
+
+    for distance in 1..=max_ring_distance(board.len()) {
+        let statement = HunterStatement { distance }.into();
+        let check = |lying: bool| {
+            can_produce_statement(
+                Hunter,
+                lying,
+                &board,
+                &board,
+                &[false; 4],
+                &[0; 4],
+                0,
+                &statement,
+            )
+        };
+        assert!(!check(false), "Truthful Hunter claimed {}", distance);
+        assert!(check(true), "Lying Hunter couldn't claim {}", distance);
+    }
+}
+
+#[test]
+fn test_bishop_target_counts() {
+    use Role::*;
+    let board = vec![Bishop, Confessor, Minion, Wretch, Baa, Lover];
+    let check = |targets: Vec<usize>, lying: bool| {
+        can_produce_statement(
+            Bishop,
+            lying,
+            &board,
+            &board,
+            &[false; 6],
+            &[0; 6],
+            0,
+            &BishopStatement {
+                target_indexes: to_bitvec(targets),
+            }
+            .into(),
+        )
+    };
+
+    assert!(check(vec![1, 2], false));
+    assert!(check(vec![1, 2, 3], false));
+    assert!(!check(vec![1, 2, 3, 5], false));
+    assert!(!check(vec![1, 3], false));
+
+    // A named Demon doesn't fit the composition, and a liar only names villagers
+    assert!(!check(vec![1, 2, 4], false));
+    assert!(!check(vec![1, 2, 4], true));
+    assert!(check(vec![1, 5], true));
+
+    let statement = BishopStatement {
+        target_indexes: to_bitvec(vec![1, 2, 3]),
+    };
+    assert_eq!(
+        statement.to_string(),
+        "Among 1, 2, 3 there is 1 villager, 1 minion, and maybe 1 outcast"
+    );
+    assert_eq!(
+        Bishop.parse_natural_statement("#2, #3, #4 or #6").unwrap(),
+        BishopStatement {
+            target_indexes: to_bitvec(vec![1, 2, 3, 5]),
+        }
+        .into()
+    );
+}
+
+#[test]
+fn test_jester_two_seats() {
+    use Role::*;
+    let statement = Jester
+        .parse_natural_statement("Among #2 and #4 there is 1 Evil")
+        .unwrap();
+    assert_eq!(
+        statement,
+        JesterStatement {
+            target_indexes: to_bitvec(vec![1, 3]),
+            evil_count: 1,
+        }
+        .into()
+    );
+    assert_eq!(
+        Jester
+            .parse_natural_statement("Among #1, #3 and #5 there are 2 Evils")
+            .unwrap(),
+        JesterStatement {
+            target_indexes: to_bitvec(vec![0, 2, 4]),
+            evil_count: 2,
+        }
+        .into()
+    );
+
+    let check = |board: &[Role], lying: bool| {
+        can_produce_statement(
+            Jester,
+            lying,
+            board,
+            board,
+            &[false; 5],
+            &[0; 5],
+            0,
+            &statement,
+        )
+    };
+    let board = vec![Jester, Confessor, Lover, Minion, Knight];
+    assert!(check(&board, false));
+    assert!(!check(&board, true));
+
+    let board = vec![Jester, Minion, Lover, Minion, Knight];
+    assert!(!check(&board, false));
+    assert!(check(&board, true));
+}
+
+#[test]
+fn test_lying_hunter_distance_bound() {
+    use Role::*;
+    let board = vec![Minion, Confessor, Lover, Baa, Knight, Confessor];
+    let check = |distance: usize| {
+        can_produce_statement(
+            Hunter,
+            true,
+            &board,
+            &[Hunter, Confessor, Lover, Knight, Knight, Confessor],
+            &[false; 6],
+            &[0; 6],
+            0,
+            &HunterStatement { distance }.into(),
+        )
+    };
+
+    // The closest other Evil is 3 seats away, the farthest any seat can be
+    assert!(!check(3));
+    assert!(check(1));
+    assert!(check(2));
+    assert!(!check(0));
+    assert!(!check(4));
+    assert!(!check(999));
+}
+
+#[test]
+fn test_plague_doctor_typed_and_natural_agree() {
+    use Role::*;
+    let typed = PlagueDoctor.parse_statement("0;2").unwrap();
+    let natural = PlagueDoctor
+        .parse_natural_statement("#1 is Evil. #3 is Corrupt")
+        .unwrap();
+    let expected: RoleStatement = PlagueDoctorStatement {
+        corruption_index: 2,
+        evil_index: Some(0),
+    }
+    .into();
+    assert_eq!(typed, expected);
+    assert_eq!(natural, expected);
+    assert_eq!(typed.to_string(), "0 is evil. 2 is corrupt");
+
+    assert_eq!(
+        PlagueDoctor.parse_statement("2").unwrap(),
+        PlagueDoctor
+            .parse_natural_statement("#3 is not Corrupt")
+            .unwrap()
+    );
+
+    // The evil seat has to be evil and the corrupt one corrupted, not the other way around
+    let board = vec![PlagueDoctor, Confessor, Minion, Lover];
+    let corruptions = vec![false, true, false, false];
+    let check = |statement: &RoleStatement| {
+        can_produce_statement(
+            PlagueDoctor,
+            false,
+            &board,
+            &board,
+            &corruptions,
+            &[0; 4],
+            0,
+            statement,
+        )
+    };
+    assert!(check(&PlagueDoctor.parse_statement("2;1").unwrap()));
+    assert!(!check(&PlagueDoctor.parse_statement("1;2").unwrap()));
+}
+
+#[test]
+fn test_alignment_helpers() {
+    use strum::IntoEnumIterator;
+    assert_eq!(Alignment::Good.opposite(), Alignment::Evil);
+    assert_eq!(Alignment::Evil.opposite(), Alignment::Good);
+    assert!(Alignment::Evil.is_evil());
+    assert!(!Alignment::Good.is_evil());
+    for role in Role::iter() {
+        assert_eq!(
+            role.group().default_alignment(),
+            role.alignment(),
+            "{:?}",
+            role
+        );
+    }
+}
+
+#[test]
+fn test_corruption_sources() {
+    use strum::IntoEnumIterator;
+    use Role::*;
+    for role in Role::iter() {
+        assert_eq!(
+            role.is_corruption_source(),
+            matches!(role, Drunk | Pooka | Poisoner | PlagueDoctor),
+            "{:?}",
+            role
+        );
+        assert_eq!(
+            role.is_uncorruption_source(),
+            role == Alchemist,
+            "{:?}",
+            role
+        );
+    }
+}
+
+#[test]
+fn test_statement_help() {
+    use strum::IntoEnumIterator;
+    for role in Role::iter() {
+        let unsupported = role
+            .parse_statement("")
+            .is_err_and(|e| e == StatementParseError::UnsupportedRole(role));
+        let Some(help) = role.statement_help() else {
+            assert!(unsupported, "{:?} has no statement help", role);
+            continue;
+        };
+        assert!(!unsupported, "{:?} can't parse statements", role);
+        let (name, _) = help.split_once(':').unwrap();
+        assert_eq!(name.parse::<Role>(), Ok(role), "{}", help);
+
+        // The example has to be something the parser takes
+        if let Some((_, example)) = help.split_once("e.g. '") {
+            let example = &example[..example.find('\'').unwrap()];
+            assert!(
+                role.parse_statement(example).is_ok(),
+                "{:?} rejects its example '{}'",
+                role,
+                example
+            );
+        }
+    }
+}
+
+#[test]
+fn test_input_string_round_trip() {
+    use strum::IntoEnumIterator;
+    use Role::*;
+    let statements: Vec<(Role, RoleStatement)> = vec![
+        (Alchemist, AlchemistStatement { corrupt_count: 2 }.into()),
+        (Architect, ArchitectStatement::Left.into()),
+        (Architect, ArchitectStatement::Equal.into()),
+        (Bard, BardStatement { distance: None }.into()),
+        (Bard, BardStatement { distance: Some(2) }.into()),
+        (
+            Bishop,
+            BishopStatement {
+                target_indexes: to_bitvec(vec![0, 2, 4]),
+            }
+            .into(),
+        ),
+        (Confessor, ConfessorStatement::IAmDizzy.into()),
+        (
+            Dreamer,
+            DreamerStatement {
+                target_index: 3,
+                role: TwinMinion,
+                negated: true,
+            }
+            .into(),
+        ),
+        (
+            Druid,
+            DruidStatement {
+                target_indexes: to_bitvec(vec![1, 5]),
+                role: Some(Wretch),
+            }
+            .into(),
+        ),
+        (
+            Empress,
+            EmpressStatement {
+                target_indexes: to_bitvec(vec![1, 2, 3]),
+            }
+            .into(),
+        ),
+        (Enlightened, EnlightenedStatement::CounterClockwise.into()),
+        (
+            FortuneTeller,
+            FortuneTellerStatement {
+                target_indexes: to_bitvec(vec![1, 3]),
+                is_evil: true,
+            }
+            .into(),
+        ),
+        (Gemcrafter, GemcrafterStatement { target_index: 2 }.into()),
+        (Hunter, HunterStatement { distance: 3 }.into()),
+        (
+            Jester,
+            JesterStatement {
+                target_indexes: to_bitvec(vec![0, 2, 4]),
+                evil_count: 1,
+            }
+            .into(),
+        ),
+        (
+            Judge,
+            JudgeStatement {
+                target_index: 3,
+                is_lying: true,
+            }
+            .into(),
+        ),
+        (Knitter, KnitterStatement { adjacent_count: 1 }.into()),
+        (Lover, LoverStatement { evil_count: 2 }.into()),
+        (
+            Medium,
+            MediumStatement {
+                target_index: 3,
+                role: PlagueDoctor,
+            }
+            .into(),
+        ),
+        (
+            Oracle,
+            OracleStatement {
+                target_indexes: to_bitvec(vec![1, 3]),
+                claim: OracleClaim::Group(Group::Outcast),
+            }
+            .into(),
+        ),
+        (
+            Oracle,
+            OracleStatement {
+                target_indexes: to_bitvec(vec![1, 3]),
+                claim: OracleClaim::Role(Minion),
+            }
+            .into(),
+        ),
+        (Poet, RoleStatement::NoStatement),
+        (
+            Scout,
+            ScoutStatement {
+                role: Some(Minion),
+                distance: 2,
+            }
+            .into(),
+        ),
+        (
+            Scout,
+            ScoutStatement {
+                role: None,
+                distance: 0,
+            }
+            .into(),
+        ),
+        (
+            Slayer,
+            SlayerStatement {
+                target_index: 3,
+                alignment: Alignment::Evil,
+            }
+            .into(),
+        ),
+        (
+            PlagueDoctor,
+            PlagueDoctorStatement {
+                corruption_index: 4,
+                evil_index: Some(1),
+            }
+            .into(),
+        ),
+        (
+            PlagueDoctor,
+            PlagueDoctorStatement {
+                corruption_index: 4,
+                evil_index: None,
+            }
+            .into(),
+        ),
+    ];
+
+    for (role, statement) in &statements {
+        let input = statement.to_input_string().unwrap();
+        assert_eq!(
+            role.parse_statement(&input).as_ref(),
+            Ok(statement),
+            "{:?} '{}'",
+            role,
+            input
+        );
+    }
+
+    // Every role that takes statements with something to say is covered
+    for role in Role::iter().filter(|role| role.statement_help().is_some()) {
+        assert!(
+            statements.iter().any(|(covered, _)| *covered == role)
+                || role.parse_statement("") == Ok(RoleStatement::NoStatement),
+            "{:?} isn't covered",
+            role
+        );
+    }
+
+    let druid: RoleStatement = DruidStatement {
+        target_indexes: to_bitvec(vec![1, 5]),
+        role: None,
+    }
+    .into();
+    assert_eq!(druid.to_input_string(), None);
+    let only_one_evil: RoleStatement = ScoutStatement {
+        role: None,
+        distance: 0,
+    }
+    .into();
+    assert_eq!(only_one_evil.to_input_string().as_deref(), Some("none"));
+    assert_eq!(Scout.parse_statement("none"), Ok(only_one_evil));
+    // "none" has no room for a distance, so one that isn't 0 isn't written
+    let with_distance: RoleStatement = ScoutStatement {
+        role: None,
+        distance: 2,
+    }
+    .into();
+    assert_eq!(with_distance.to_input_string(), None);
+    let oracle: RoleStatement = OracleStatement {
+        target_indexes: to_bitvec(vec![1, 3]),
+        claim: OracleClaim::Group(Group::Minion),
+    }
+    .into();
+    assert_eq!(
+        oracle.to_input_string().as_deref(),
+        Some("1,3;group:minion")
+    );
+    assert_eq!(Oracle.parse_statement("1,3;group:minion"), Ok(oracle));
+}
+
+#[test]
+fn test_statement_parse_errors() {
+    use Role::*;
+    for role in [Knight, Baker, Poet, Bombardier, Wretch] {
+        assert_eq!(role.parse_statement(""), Ok(RoleStatement::NoStatement));
+        assert_eq!(
+            role.parse_statement("1"),
+            Err(StatementParseError::UnexpectedStatement(role))
+        );
+        assert_eq!(
+            role.parse_natural_statement("One is Evil: #1, #2 or #3"),
+            Err(StatementParseError::UnexpectedStatement(role))
+        );
+    }
+    assert_eq!(
+        Knight.parse_statement("0,2,4").unwrap_err().to_string(),
+        "Knight makes no statement; use '?'"
+    );
+    assert_eq!(
+        Minion.parse_statement("1"),
+        Err(StatementParseError::UnsupportedRole(Minion))
+    );
+    assert_eq!(
+        Medium.parse_statement("1;hunter;2"),
+        Err(StatementParseError::WrongArgCount {
+            expected: 2,
+            got: 3,
+            input: "1;hunter;2".to_string(),
+        })
+    );
+    assert_eq!(
+        Medium
+            .parse_statement("1;hunter;2")
+            .unwrap_err()
+            .to_string(),
+        "Invalid statement '1;hunter;2' - expected 2 parts separated by ';', got 3"
+    );
+    // Too many parts is reported even when one of them isn't a seat
+    assert_eq!(
+        PlagueDoctor.parse_statement("1;2;x"),
+        Err(StatementParseError::WrongArgCount {
+            expected: 2,
+            got: 3,
+            input: "1;2;x".to_string(),
+        })
+    );
+    assert!(matches!(
+        Medium.parse_statement("x;hunter"),
+        Err(StatementParseError::InvalidIndex(_))
+    ));
+    assert!(matches!(
+        Empress.parse_statement("1,2,99"),
+        Err(StatementParseError::InvalidIndex(_))
+    ));
+    assert!(matches!(
+        Medium.parse_statement("1;nobody"),
+        Err(StatementParseError::InvalidRole(_))
+    ));
+    assert!(matches!(
+        Slayer.parse_statement("1;neutral"),
+        Err(StatementParseError::InvalidKeyword(_))
+    ));
+    assert!(matches!(
+        Hunter.parse_natural_statement("I have no idea"),
+        Err(StatementParseError::InvalidKeyword(_))
+    ));
+    assert!(matches!(
+        Empress.parse_statement_one_based("0,1"),
+        Err(StatementParseError::InvalidIndex(_))
+    ));
+
+    // The messages read the same as before
+    assert_eq!(
+        Slayer.parse_statement("1;neutral").unwrap_err().to_string(),
+        "Invalid alignment 'neutral' in Slayer statement - expected 'good' or 'evil'"
+    );
+}
+
+#[test]
+fn test_natural_statements_reject_missing_seats() {
+    use Role::*;
+    for (role, text) in [
+        (Bishop, "#0, #3, #4 or #6"),
+        (Bishop, "#2, #3, #4 or #17"),
+        (Jester, "Among #0 and #4 there is 1 Evil"),
+        (Jester, "Among #1, #3 and #17 there are 2 Evils"),
+        (Druid, "Among #0, #3 there is: Hunter"),
+        (Druid, "Among #2, #3, #17 there are NO Outcasts"),
+        (Slayer, "I killed Evil #0"),
+        (Slayer, "I couldn't kill #17"),
+        (Dreamer, "#0 could be: Minion"),
+        (Dreamer, "#17 could not be: Minion"),
+    ] {
+        assert!(
+            matches!(
+                role.parse_natural_statement(text),
+                Err(StatementParseError::InvalidIndex(_))
+            ),
+            "{:?}: {}",
+            role,
+            text
+        );
+    }
+    assert_eq!(
+        Bishop
+            .parse_natural_statement("#2 or #17")
+            .unwrap_err()
+            .to_string(),
+        "Seat #17 in '#2 or #17' doesn't exist, seats are numbered from 1 to 16"
+    );
+}
+
+#[test]
+fn test_knitter_natural_statement() {
+    for (text, adjacent_count) in [
+        ("There is 1 Evil pair", 1),
+        ("There are 2 Evil pairs", 2),
+        ("Evils are not adjacent to eachother", 0),
+    ] {
+        let statement: RoleStatement = KnitterStatement { adjacent_count }.into();
+        assert_eq!(
+            Role::Knitter.parse_natural_statement(text),
+            Ok(statement.clone()),
+            "{}",
+            text
+        );
+        if adjacent_count > 0 {
+            assert_eq!(statement.to_string(), text);
+        }
+    }
+
+    // Other numbers in the sentence aren't taken for the pair count
+    assert!(Role::Knitter
+        .parse_natural_statement("there are 2 evils, 1 pair adjacent")
+        .is_err());
+}
+
+#[test]
+fn test_count_evil_pairs_wraps_around() {
+    use Role::*;
+    let board = vec![Minion, Confessor, Knight, Lover, Baa];
+    assert_eq!(count_evil_pairs(&board), 1);
+    assert_eq!(count_evil_pairs_on(&board, Topology::Line), 0);
+
+    // Two seats are only one pair, not one each way
+    assert_eq!(count_evil_pairs(&[Minion, Baa]), 1);
+
+    let statement = KnitterStatement { adjacent_count: 1 }.into();
+    assert!(can_produce_statement(
+        Knitter,
+        false,
+        &board,
+        &board,
+        &[false; 5],
+        &[0; 5],
+        1,
+        &statement,
+    ));
+}
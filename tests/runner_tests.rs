@@ -0,0 +1,704 @@
+use demon_deduce::roles::*;
+use demon_deduce::{
+    parse_cli_puzzle, parse_clipboard_puzzle, poll_clipboard, run_args_to, run_clipboard_loop_with,
+    ClipboardSource, Puzzle, Role, RunOutcome,
+};
+use std::collections::VecDeque;
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+fn run_with_outcome(args: &[&str]) -> (RunOutcome, String) {
+    let mut out = Vec::new();
+    let outcome = run_args_to(args.iter().map(|a| a.to_string()).collect(), &mut out).unwrap();
+    (outcome, String::from_utf8(out).unwrap())
+}
+
+fn run(args: &[&str]) -> String {
+    run_with_outcome(args).1
+}
+
+fn solution_lines(output: &str) -> usize {
+    output
+        .lines()
+        .skip_while(|l| !l.starts_with("Found"))
+        .skip(1)
+        .take_while(|l| !l.is_empty())
+        .count()
+}
+
+#[test]
+fn test_all_flag_prints_every_solution() {
+    let args = [
+        "prog",
+        "confessor,hunter,lover,knight,minion",
+        "4",
+        "0",
+        "1",
+        "0",
+        "?",
+        "?",
+        "?",
+        "?",
+        "?",
+    ];
+
+    let output = run(&args);
+    assert!(output.contains("Found 120 solution(s)"), "{}", output);
+    assert_eq!(solution_lines(&output), 0, "{}", output);
+
+    let output = run(&[&args[..], &["--all"]].concat());
+    assert_eq!(solution_lines(&output), 120, "{}", output);
+
+    let output = run(&[&args[..], &["--limit", "7"]].concat());
+    assert_eq!(solution_lines(&output), 7, "{}", output);
+
+    // Either order, the two flags ask for different listings
+    for flags in [["--all", "--limit", "7"], ["--limit", "7", "--all"]] {
+        let (outcome, output) = run_with_outcome(&[&args[..], &flags].concat());
+        assert_eq!(outcome, RunOutcome::InvalidInput, "{}", output);
+        assert!(
+            output.contains("Error: --all and --limit can't be used together"),
+            "{}",
+            output
+        );
+    }
+}
+
+#[test]
+fn test_outcome_reflects_solution_count() {
+    let confessors = [
+        "prog",
+        "confessor,confessor,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor::iamgood",
+        "confessor::iamgood",
+        "confessor::iamdizzy",
+    ];
+    let (outcome, _) = run_with_outcome(&confessors);
+    assert_eq!(outcome, RunOutcome::Unique);
+    assert_eq!(outcome.exit_code(), 0);
+
+    let contradiction = [
+        "prog",
+        "confessor,confessor,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor::iamgood",
+        "confessor::iamgood",
+        "confessor::iamgood",
+    ];
+    let (outcome, output) = run_with_outcome(&contradiction);
+    assert_eq!(outcome, RunOutcome::NoSolutions, "{}", output);
+    assert_eq!(outcome.exit_code(), 2);
+
+    let unrevealed = [
+        "prog",
+        "confessor,confessor,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "?",
+        "?",
+        "?",
+    ];
+    let (outcome, _) = run_with_outcome(&unrevealed);
+    assert_eq!(outcome, RunOutcome::MultipleSolutions);
+    assert_eq!(outcome.exit_code(), 3);
+
+    let (outcome, _) = run_with_outcome(&["prog", "confessor"]);
+    assert_eq!(outcome, RunOutcome::InvalidInput);
+    assert_eq!(outcome.exit_code(), 1);
+
+    // Bad flags are reported rather than exiting the process
+    for (flags, error) in [
+        (
+            vec!["--limit", "many"],
+            "Error: --limit requires a number of solutions to print",
+        ),
+        (
+            vec!["--validate", "confessor,dancer,minion"],
+            "Failed to parse candidate roles",
+        ),
+    ] {
+        let args: Vec<&str> = confessors.iter().copied().chain(flags).collect();
+        let (outcome, output) = run_with_outcome(&args);
+        assert_eq!(outcome, RunOutcome::InvalidInput, "{}", output);
+        assert!(output.contains(error), "{}", output);
+    }
+    let (outcome, output) = run_with_outcome(&["prog", "--validate"]);
+    assert_eq!(outcome, RunOutcome::InvalidInput, "{}", output);
+    assert!(
+        output.contains("Error: --validate requires a candidate argument"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_binary_reads_counts_in_order() {
+    // Villagers, outcasts, minions and demons, so this puts the Wretch in play
+    // and leaves the Minion out
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_demon_deduce"))
+        .args([
+            "confessor,lover,bard,wretch,minion",
+            "3",
+            "1",
+            "0",
+            "0",
+            "confessor::iamgood",
+            "?",
+            "?",
+            "?",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(3), "{}", stdout);
+    assert!(stdout.contains("Wretch"), "{}", stdout);
+    assert!(!stdout.contains("Minion"), "{}", stdout);
+}
+
+#[test]
+fn test_reports_forced_liars() {
+    let args = [
+        "prog",
+        "scout,confessor,lover,minion,witch",
+        "2",
+        "0",
+        "2",
+        "0",
+        "scout::none",
+        "?",
+        "?",
+        "?",
+    ];
+    let note = "Seat 1 can't be telling the truth with 2 evil seats in play";
+    let output = run(&args);
+    let found = output.find("Found ").expect(&output);
+    assert!(output[found..].contains(note), "{}", output);
+
+    let quiet: Vec<&str> = args.iter().copied().chain(["--quiet"]).collect();
+    assert!(!run(&quiet).contains(note));
+
+    // A valid-looking board with the Scout telling the truth
+    let validate: Vec<&str> = args
+        .iter()
+        .copied()
+        .chain(["--validate", "scout,confessor,minion,witch"])
+        .collect();
+    let output = run(&validate);
+    assert!(output.contains("Candidate is invalid"), "{}", output);
+    assert!(output.contains(note), "{}", output);
+}
+
+#[test]
+fn test_parse_cli_puzzle() {
+    let args: Vec<String> = [
+        "prog",
+        "confessor,minion",
+        "1",
+        "0",
+        "1",
+        "0",
+        "confessor::iamgood",
+    ]
+    .iter()
+    .map(|a| a.to_string())
+    .collect();
+
+    assert_eq!(
+        parse_cli_puzzle(&args),
+        Ok(Puzzle {
+            deck: vec![Role::Confessor, Role::Minion],
+            visible: vec![Some(Role::Confessor)],
+            confirmed: vec![None],
+            observed: vec![ConfessorStatement::IAmGood.into()],
+            villagers: 1,
+            outcasts: 0,
+            minions: 1,
+            demons: 0,
+        })
+    );
+
+    assert!(parse_cli_puzzle(&args[..3]).is_err());
+}
+
+#[test]
+fn test_parse_deck_multipliers() {
+    let parse_deck = |deck: &str| {
+        let args: Vec<String> = ["prog", deck, "1", "0", "1", "0"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        parse_cli_puzzle(&args).map(|puzzle| puzzle.deck)
+    };
+
+    let expected = vec![
+        Role::Confessor,
+        Role::Confessor,
+        Role::Confessor,
+        Role::Minion,
+    ];
+    assert_eq!(parse_deck("confessor x3, minion"), Ok(expected.clone()));
+    assert_eq!(parse_deck("3*confessor,minion"), Ok(expected.clone()));
+    assert_eq!(
+        parse_deck("confessor,confessor,confessor,minion"),
+        Ok(expected)
+    );
+
+    let err = parse_deck("confessor xthree,minion").unwrap_err();
+    assert!(err.contains("Invalid multiplier 'three'"), "{}", err);
+    let err = parse_deck("0*confessor,minion").unwrap_err();
+    assert!(err.contains("Invalid multiplier '0'"), "{}", err);
+}
+
+#[test]
+fn test_statements_may_name_unrevealed_seats() {
+    let expected: RoleStatement = EmpressStatement {
+        target_indexes: to_bitvec(vec![1, 2, 3]),
+    }
+    .into();
+
+    let args = [
+        "prog",
+        "empress,confessor,lover,knight,minion",
+        "3",
+        "0",
+        "1",
+        "0",
+        "empress::1,2,3",
+        "?",
+        "?",
+        "?",
+    ];
+    let args_owned: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let puzzle = parse_cli_puzzle(&args_owned).unwrap();
+    assert_eq!(puzzle.visible, vec![Some(Role::Empress), None, None, None]);
+    assert_eq!(puzzle.observed[0], expected);
+    let (outcome, output) = run_with_outcome(&args);
+    assert_eq!(outcome, RunOutcome::MultipleSolutions, "{}", output);
+
+    let content =
+        "empress,confessor,lover,knight,minion\n3 0 1 0\n1|empress||One is Evil: #2, #3 or #4";
+    let puzzle = parse_clipboard_puzzle(content).unwrap();
+    assert_eq!(puzzle.visible, vec![Some(Role::Empress), None, None, None]);
+    assert_eq!(puzzle.observed[0], expected);
+
+    // Only the speaker has to be revealed
+    let mut unrevealed_speaker = args_owned.clone();
+    unrevealed_speaker[6] = "?::1,2,3".to_string();
+    let err = parse_cli_puzzle(&unrevealed_speaker).unwrap_err();
+    assert!(
+        err.contains("Cannot provide statement for unrevealed role"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_clipboard_seat_count_mismatch() {
+    let content =
+        "confessor,lover,hunter,knight,bard,minion\n5 0 1 0\n1|confessor||I am Good\n8|lover||";
+    let errors = parse_clipboard_puzzle(content).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "Index 8 out of bounds (must be 1-6, the counts add up to 6 seats: 5 villagers, \
+             0 outcasts, 1 minions, 0 demons) in line: 8|lover||"
+        ]
+    );
+
+    // An explicit seat count has to agree with the counts
+    let content = "confessor,lover,hunter,knight,bard,minion\n5 0 1 0 7\n1|confessor||I am Good";
+    assert!(parse_clipboard_puzzle(content).is_err());
+
+    let content = "confessor,lover,hunter,knight,bard,minion\n5 0 1 0 6\n1|confessor||I am Good";
+    let puzzle = parse_clipboard_puzzle(content).unwrap();
+    assert_eq!(puzzle.visible.len(), 6);
+    assert_eq!(puzzle.visible[0], Some(Role::Confessor));
+}
+
+#[test]
+fn test_seats_flag_cross_checks_counts() {
+    let args = [
+        "prog",
+        "confessor,minion",
+        "1",
+        "0",
+        "1",
+        "0",
+        "confessor::iamgood",
+        "?",
+    ];
+    let (outcome, _) = run_with_outcome(&[&args[..], &["--seats", "2"]].concat());
+    assert_eq!(outcome, RunOutcome::Unique);
+
+    let (outcome, output) = run_with_outcome(&[&args[..], &["--seats", "3"]].concat());
+    assert_eq!(outcome, RunOutcome::InvalidInput);
+    assert!(output.contains("Expected 3 seats"), "{}", output);
+
+    let (outcome, output) = run_with_outcome(&[&args[..], &["--seats", "two"]].concat());
+    assert_eq!(outcome, RunOutcome::InvalidInput);
+    assert!(
+        output.contains("Error: --seats requires the number of seats"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_roles_not_in_deck() {
+    let args: Vec<String> = [
+        "prog",
+        "confessor,lover,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor::iamgood",
+        "lover:hunter:1",
+        "?",
+    ]
+    .iter()
+    .map(|a| a.to_string())
+    .collect();
+    let err = parse_cli_puzzle(&args).unwrap_err();
+    assert!(
+        err.contains("Seat 2 is confirmed as Hunter, which is not in deck"),
+        "{}",
+        err
+    );
+
+    let content = "confessor,lover,minion\n2 0 1 0\n1|confessor||I am Good\n2|hunter||";
+    let errors = parse_clipboard_puzzle(content).unwrap_err();
+    assert_eq!(errors, vec!["Seat 2 shows Hunter, which is not in deck"]);
+}
+
+#[test]
+fn test_validate_reports_every_problem() {
+    let puzzle = Puzzle {
+        deck: vec![Role::Confessor, Role::Gemcrafter, Role::Minion],
+        visible: vec![
+            Some(Role::Confessor),
+            Some(Role::Gemcrafter),
+            Some(Role::Hunter),
+        ],
+        confirmed: vec![None; 3],
+        observed: vec![
+            ConfessorStatement::IAmGood.into(),
+            GemcrafterStatement { target_index: 5 }.into(),
+            RoleStatement::NoStatement,
+        ],
+        villagers: 2,
+        outcasts: 0,
+        minions: 2,
+        demons: 0,
+    };
+    assert_eq!(
+        puzzle.validate().unwrap_err(),
+        vec![
+            "The counts add up to 4 but there are 3 seats",
+            "Seat 2 refers to a seat past the 3 in play",
+            "Seat 3 shows Hunter, which is not in deck",
+        ]
+    );
+
+    let fixed = Puzzle {
+        visible: vec![Some(Role::Confessor), Some(Role::Gemcrafter), None],
+        observed: vec![
+            ConfessorStatement::IAmGood.into(),
+            GemcrafterStatement { target_index: 0 }.into(),
+            RoleStatement::NoStatement,
+        ],
+        minions: 1,
+        ..puzzle
+    };
+    assert_eq!(fixed.validate(), Ok(()));
+}
+
+#[test]
+fn test_explain_marks_corrupted_and_disguised_seats() {
+    let args = [
+        "prog",
+        "confessor,knight,lover,poisoner",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor:confessor:iamdizzy",
+        "knight:knight",
+        "lover",
+    ];
+    let output = run(&[&args[..], &["--explain"]].concat());
+    assert!(
+        output.contains("Confessor*, Knight, Poisoner(as Lover)\n"),
+        "{}",
+        output
+    );
+
+    let output = run(&args);
+    assert!(!output.contains("(as Lover)"), "{}", output);
+}
+
+#[test]
+fn test_time_prints_search_counts() {
+    let args = [
+        "prog",
+        "--time",
+        "confessor,knight,lover,poisoner",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor:confessor:iamdizzy",
+        "knight:knight",
+        "lover",
+    ];
+    let output = run(&args);
+    assert!(output.contains("Solved in "), "{}", output);
+    assert!(output.contains(", 1 accepted\n"), "{}", output);
+    assert!(output.contains("Found 1 solution(s)"), "{}", output);
+
+    let output = run(&args[..1]
+        .iter()
+        .chain(&args[2..])
+        .copied()
+        .collect::<Vec<_>>());
+    assert!(!output.contains("Solved in "), "{}", output);
+}
+
+#[test]
+fn test_csv_output() {
+    let (outcome, output) = run_with_outcome(&[
+        "prog",
+        "--csv",
+        "confessor,knight,lover,poisoner",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor::iamdizzy",
+        "knight:knight",
+        "lover",
+    ]);
+    assert_eq!(outcome, RunOutcome::MultipleSolutions);
+    assert_eq!(
+        output,
+        "seat_1,seat_2,seat_3\nConfessor,Knight,Poisoner\nPoisoner,Knight,Lover\n"
+    );
+
+    // Without solutions there's still a header
+    let (outcome, output) = run_with_outcome(&[
+        "prog",
+        "--csv",
+        "confessor,knight,lover,poisoner",
+        "2",
+        "0",
+        "1",
+        "0",
+        "confessor::iamgood",
+        "confessor::iamgood",
+        "confessor::iamgood",
+    ]);
+    assert_eq!(outcome, RunOutcome::NoSolutions, "{}", output);
+    assert_eq!(output, "seat_1,seat_2,seat_3\n");
+}
+
+#[test]
+fn test_demon_summary() {
+    let output = run(&[
+        "prog",
+        "confessor,lover,knight,baa",
+        "2",
+        "0",
+        "0",
+        "1",
+        "confessor::iamgood",
+        "?",
+        "?",
+    ]);
+    assert!(
+        output.contains("Demon could be seat 2 (Baa) in 2 boards, seat 3 (Baa) in 2 boards"),
+        "{}",
+        output
+    );
+    assert!(output.contains("Seat 1: definitely Good"), "{}", output);
+    assert!(!output.contains("Seat 2: definitely"), "{}", output);
+}
+
+#[test]
+fn test_quiet_flag() {
+    let args = [
+        "prog",
+        "confessor,lover,knight,baa",
+        "2",
+        "0",
+        "0",
+        "1",
+        "confessor::iamgood",
+        "?",
+        "?",
+        "--quiet",
+    ];
+    // Without the flag the solutions and the notes after the summary show up
+    let loud = run(&args[..args.len() - 1]);
+    assert_eq!(solution_lines(&loud), 4, "{}", loud);
+    assert!(loud.contains("Demon could be"), "{}", loud);
+    assert!(loud.contains("Seat 1: definitely Good"), "{}", loud);
+
+    let output = run(&args);
+    assert_eq!(
+        output,
+        "Found 4 solution(s)\n\
+         \n\
+         Possible roles per position:\n\
+         1: Confessor\n\
+         2: Knight, Lover, Baa\n\
+         3: Knight, Lover, Baa\n"
+    );
+
+    // Overrides the solution listing flags
+    let output = run(&[&args[..], &["--all"]].concat());
+    assert_eq!(solution_lines(&output), 0, "{}", output);
+}
+
+#[test]
+fn test_count_mismatch_is_invalid_input() {
+    let (outcome, output) = run_with_outcome(&[
+        "prog",
+        "confessor,lover,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "?",
+        "?",
+    ]);
+    assert_eq!(outcome, RunOutcome::InvalidInput);
+    assert!(
+        output.contains("Invalid puzzle: The counts add up to 3 but there are 2 seats"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_one_based_statements() {
+    assert_eq!(
+        Role::Empress.parse_statement_one_based("1,2,3"),
+        Ok(EmpressStatement {
+            target_indexes: to_bitvec(vec![0, 1, 2]),
+        }
+        .into())
+    );
+    assert_eq!(
+        Role::PlagueDoctor.parse_statement_one_based("1;3"),
+        Role::PlagueDoctor.parse_statement("0;2")
+    );
+    assert!(Role::Empress.parse_statement_one_based("0,1").is_err());
+
+    let zero_based = run(&[
+        "prog",
+        "confessor,gemcrafter,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "gemcrafter::2",
+        "?",
+        "?",
+    ]);
+    let one_based = run(&[
+        "prog",
+        "confessor,gemcrafter,minion",
+        "2",
+        "0",
+        "1",
+        "0",
+        "gemcrafter::3",
+        "?",
+        "?",
+        "--one-based",
+    ]);
+    assert!(zero_based.starts_with("Found"), "{}", zero_based);
+    assert_eq!(zero_based, one_based);
+}
+
+/// Clipboard that hands out scripted reads, `None` for a failed one
+struct ScriptedClipboard(VecDeque<Option<String>>);
+
+impl ClipboardSource for ScriptedClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.0.pop_front().flatten()
+    }
+}
+
+#[test]
+fn test_clipboard_error_keeps_last_content() {
+    let mut clipboard = ScriptedClipboard(
+        [Some("first"), None, Some("first"), Some("second")]
+            .into_iter()
+            .map(|read| read.map(str::to_string))
+            .collect(),
+    );
+    let mut last_content = String::new();
+
+    let polls: Vec<Option<String>> = (0..4)
+        .map(|_| poll_clipboard(&mut clipboard, &mut last_content))
+        .collect();
+    // The failed read isn't taken as an empty clipboard, so "first" isn't new again
+    assert_eq!(
+        polls,
+        vec![
+            Some("first".to_string()),
+            None,
+            None,
+            Some("second".to_string())
+        ]
+    );
+    assert_eq!(last_content, "second");
+}
+
+#[test]
+fn test_clipboard_loop_solves_each_new_puzzle() {
+    let first = "confessor,lover,minion\n2 0 1 0\n1|confessor||I am Good";
+    let second = "confessor,lover,minion\n2 0 1 0\n2|lover||";
+    let mut clipboard = ScriptedClipboard(
+        [
+            Some(first),
+            Some(first),
+            None,
+            Some("not a puzzle"),
+            Some(second),
+        ]
+        .into_iter()
+        .map(|read| read.map(str::to_string))
+        .collect(),
+    );
+
+    let mut solved = Vec::new();
+    run_clipboard_loop_with(&mut clipboard, Duration::ZERO, |puzzle| {
+        solved.push(puzzle.clone());
+        if solved.len() == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+
+    assert_eq!(
+        solved,
+        vec![
+            parse_clipboard_puzzle(first).unwrap(),
+            parse_clipboard_puzzle(second).unwrap()
+        ]
+    );
+    assert!(clipboard.0.is_empty());
+}
@@ -0,0 +1,176 @@
+use demon_deduce::roles::*;
+use demon_deduce::{decode_puzzle, encode_puzzle, Puzzle, Role};
+
+fn twin_and_medium() -> Puzzle {
+    use Role::*;
+    Puzzle {
+        deck: vec![
+            Judge,
+            Lover,
+            Gemcrafter,
+            Enlightened,
+            Medium,
+            Wretch,
+            Minion,
+            TwinMinion,
+        ],
+        visible: vec![
+            Some(Medium),
+            Some(Judge),
+            Some(Gemcrafter),
+            Some(Lover),
+            Some(Gemcrafter),
+            None,
+            None,
+        ],
+        confirmed: vec![None; 7],
+        observed: vec![
+            MediumStatement {
+                target_index: 2,
+                role: Gemcrafter,
+            }
+            .into(),
+            JudgeStatement {
+                target_index: 0,
+                is_lying: true,
+            }
+            .into(),
+            GemcrafterStatement { target_index: 0 }.into(),
+            LoverStatement { evil_count: 1 }.into(),
+            GemcrafterStatement { target_index: 3 }.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ],
+        villagers: 4,
+        outcasts: 1,
+        minions: 2,
+        demons: 0,
+    }
+}
+
+#[test]
+fn test_twin_and_medium_round_trip() {
+    let puzzle = twin_and_medium();
+    let token = encode_puzzle(&puzzle);
+    assert!(
+        token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "{}",
+        token
+    );
+    assert!(token.len() < 64, "{}", token);
+    assert_eq!(decode_puzzle(&token), Ok(puzzle));
+}
+
+#[test]
+fn test_every_statement_round_trips() {
+    use Role::*;
+    let observed: Vec<RoleStatement> = vec![
+        AlchemistStatement { corrupt_count: 2 }.into(),
+        ArchitectStatement::Equal.into(),
+        BardStatement { distance: None }.into(),
+        BishopStatement {
+            target_indexes: to_bitvec(vec![0, 9, 15]),
+        }
+        .into(),
+        ConfessorStatement::IAmDizzy.into(),
+        DruidStatement {
+            target_indexes: to_bitvec(vec![1, 2]),
+            role: Some(Witch),
+        }
+        .into(),
+        DreamerStatement {
+            target_index: 3,
+            role: Pooka,
+            negated: false,
+        }
+        .into(),
+        DreamerStatement {
+            target_index: 2,
+            role: Minion,
+            negated: true,
+        }
+        .into(),
+        EmpressStatement {
+            target_indexes: to_bitvec(vec![4, 5, 6]),
+        }
+        .into(),
+        EnlightenedStatement::CounterClockwise.into(),
+        FortuneTellerStatement {
+            target_indexes: to_bitvec(vec![0, 1]),
+            is_evil: true,
+        }
+        .into(),
+        HunterStatement { distance: 300 }.into(),
+        JesterStatement {
+            target_indexes: to_bitvec(vec![2, 3]),
+            evil_count: 1,
+        }
+        .into(),
+        OracleStatement {
+            target_indexes: to_bitvec(vec![1, 3]),
+            claim: OracleClaim::Role(Witch),
+        }
+        .into(),
+        OracleStatement {
+            target_indexes: to_bitvec(vec![1, 3]),
+            claim: OracleClaim::Group(Group::Outcast),
+        }
+        .into(),
+        ScoutStatement {
+            role: None,
+            distance: 2,
+        }
+        .into(),
+        SlayerStatement {
+            target_index: 1,
+            alignment: Alignment::Evil,
+        }
+        .into(),
+        PlagueDoctorStatement {
+            corruption_index: 2,
+            evil_index: Some(0),
+        }
+        .into(),
+    ];
+    let puzzle = Puzzle {
+        deck: vec![Confessor, Baa, Minion],
+        visible: vec![None; observed.len()],
+        confirmed: vec![Some(Baa); observed.len()],
+        observed,
+        villagers: 1,
+        outcasts: 0,
+        minions: 1,
+        demons: 1,
+    };
+    assert_eq!(decode_puzzle(&encode_puzzle(&puzzle)), Ok(puzzle));
+}
+
+#[test]
+fn test_decode_rejects_bad_tokens() {
+    let token = encode_puzzle(&twin_and_medium());
+    assert!(decode_puzzle("not a token!").is_err());
+    assert!(decode_puzzle(&token[..token.len() - 2]).is_err());
+    assert!(decode_puzzle(&format!("{}AAAA", token)).is_err());
+}
+
+#[test]
+fn test_every_role_keeps_its_number() {
+    use strum::IntoEnumIterator;
+    let puzzle = Puzzle {
+        deck: Role::iter().collect(),
+        visible: vec![Some(Role::Pooka)],
+        confirmed: vec![Some(Role::Alchemist)],
+        observed: vec![RoleStatement::NoStatement],
+        villagers: 0,
+        outcasts: 0,
+        minions: 0,
+        demons: 1,
+    };
+    assert_eq!(decode_puzzle(&encode_puzzle(&puzzle)), Ok(puzzle.clone()));
+
+    // Written before roles had fixed numbers, it has to keep decoding the same
+    let token = "ASgAAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyAhIiMkJSYnAAAAAQEoAQA";
+    assert_eq!(decode_puzzle(token), Ok(puzzle));
+}
@@ -1,5 +1,13 @@
 use demon_deduce::roles::*;
-use demon_deduce::{brute_force_solve, Role};
+use demon_deduce::{
+    brute_force_solve, brute_force_solve_constrained, brute_force_solve_detailed,
+    brute_force_solve_with_progress, consistent_statements_for_seat,
+    consistent_statements_for_seat_on, definite_alignments, demon_seat_distribution,
+    diagnose_infeasible, distinct_up_to_rotation, forced_liars, group_by_demon,
+    minimal_statement_set, solve_batch, solve_iter, solve_iter_shuffled, solve_with_stats,
+    summarize_positions, try_solve, unused_deck_roles, validate_candidate, validate_candidate_on,
+    Constraints, PositionSummary, Puzzle, Role, SolveError, SolverContext,
+};
 
 #[test]
 fn finds_minion_with_typed_statements() {
@@ -15,7 +23,7 @@ fn finds_minion_with_typed_statements() {
         ConfessorStatement::IAmGood.into(),
         ConfessorStatement::IAmDizzy.into(),
     ];
-    let sols = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let sols = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
     assert_eq!(sols.len(), 1);
     assert_eq!(
         sols[0],
@@ -43,7 +51,7 @@ fn example_minion_disguised_as_confessor() {
         ConfessorStatement::IAmDizzy.into(),
     ];
 
-    let sols = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let sols = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
     assert_eq!(sols.len(), 1);
     let sol = &sols[0];
     assert_eq!(sol[0], Role::Confessor);
@@ -71,7 +79,7 @@ fn example_with_claim_statement() {
         ConfessorStatement::IAmDizzy.into(),
     ];
 
-    let _ = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let _ = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
 }
 
 #[test]
@@ -87,7 +95,7 @@ fn test_iam_good_iam_dizzy_unrevealed() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[1]),
@@ -115,7 +123,7 @@ fn test_iam_good_iam_good_unrevealed() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[2]),
@@ -143,7 +151,7 @@ fn test_iam_good_claim_1_is_good_unrevealed() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[2]),
@@ -180,7 +188,7 @@ fn test_lover_lover_unrevealed_minion_unrevealed() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[3]),
@@ -217,7 +225,7 @@ fn test_lover_lover_unrevealed_unrevealed_minion() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[4]),
@@ -252,7 +260,7 @@ fn test_loverminion_lover_unrevealed_unrevealed() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[0]),
@@ -310,7 +318,7 @@ fn test_empress_empress_empress() {
         .into(),
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[0]),
@@ -356,7 +364,7 @@ fn test_hunter_lover() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[3]),
@@ -401,7 +409,7 @@ fn test_enlightened() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[4]),
@@ -451,7 +459,7 @@ fn test_wretch() {
         HunterStatement { distance: 2 }.into(),
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[4]),
@@ -508,7 +516,7 @@ fn test_twin_and_medium() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 2, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 2, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[0]),
@@ -561,7 +569,7 @@ fn test_jester() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 2, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 2, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[5]),
@@ -590,7 +598,7 @@ fn test_confirmed() {
     let confirmed = vec![Some(Knight), None];
     let observed: Vec<RoleStatement> = vec![RoleStatement::NoStatement, RoleStatement::NoStatement];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[1]),
@@ -635,7 +643,7 @@ fn test_scout() {
         EnlightenedStatement::Clockwise.into(),
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 1, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[3]),
@@ -706,7 +714,7 @@ fn test_fortune_teller() {
         .into(),
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 2, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 2, 0);
 
     for solution in &solutions {
         assert!(
@@ -741,7 +749,7 @@ fn test_counsellor() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0);
     for solution in &solutions {
         assert!(
             is_evil(&solution[3]),
@@ -798,7 +806,7 @@ fn test_scout_2() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 1, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 5, 1, 1, 1);
     for solution in &solutions {
         assert!(
             is_evil(&solution[4]),
@@ -842,7 +850,7 @@ fn test_puppet() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 0, 2, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 0, 2, 0);
     for solution in &solutions {
         assert!(
             !is_evil(&solution[1]),
@@ -887,7 +895,7 @@ fn test_puppet_in_deck_but_not_in_play() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
 
     assert!(
         solutions.is_empty(),
@@ -920,7 +928,7 @@ fn test_puppeteer_without_puppet_not_adjacent_to_villagers() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 2, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 2, 0);
 
     assert!(
         !solutions.is_empty(),
@@ -977,7 +985,7 @@ fn test_puppet_without_puppeteer_rejected() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
     // Should have no solutions since puppet requires puppeteer
     assert!(
         solutions.is_empty(),
@@ -1011,7 +1019,7 @@ fn test_puppeteer_adjacent_to_puppet() {
         RoleStatement::NoStatement,
     ];
 
-    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 2, 0, false);
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 2, 0);
 
     assert!(
         !solutions.is_empty(),
@@ -1040,3 +1048,1754 @@ fn test_puppeteer_adjacent_to_puppet() {
         );
     }
 }
+
+#[test]
+fn test_two_demons_cannot_share_disguise() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Lover, Baa, Lilis];
+    let confirmed = vec![None; 3];
+    let observed = vec![RoleStatement::NoStatement; 3];
+
+    // Both Hunters would have to be demons wearing the same disguise
+    let visible = vec![Some(Hunter), Some(Hunter), None];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 0, 0, 2);
+    assert!(
+        solutions.is_empty(),
+        "Two demons shared a disguise. Solutions: {:#?}",
+        solutions
+    );
+
+    // Distinct disguises are still fine
+    let visible = vec![Some(Hunter), Some(Lover), None];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 0, 0, 2);
+    assert!(
+        solutions.contains(&vec![Baa, Lilis, Confessor]),
+        "Missing two disguised demons. Solutions: {:#?}",
+        solutions
+    );
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_disguises_limited_by_deck_copies() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Drunk, Minion];
+    let visible = vec![Some(Hunter), Some(Hunter), Some(Confessor)];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 1, 0);
+    for solution in &solutions {
+        // The Drunk and the Minion can't both pose as the single Hunter
+        assert!(
+            solution.contains(&Hunter),
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_drunk_shows_a_villager() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Drunk, Minion];
+    let visible = vec![Some(Confessor), Some(Hunter), None];
+    let confirmed = vec![Some(Confessor), Some(Drunk), None];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    // The Drunk believes it's a villager that isn't in play, so it shows as one
+    let solutions = brute_force_solve_detailed(&deck, &visible, &confirmed, &observed, 1, 1, 1, 0);
+    assert_eq!(solutions.len(), 1, "Solutions: {:#?}", solutions);
+    assert_eq!(solutions[0].true_roles, vec![Confessor, Drunk, Minion]);
+    assert_eq!(solutions[0].disguise_at(1), Some(Hunter));
+
+    // It never shows as the Drunk itself
+    let visible = vec![Some(Confessor), Some(Drunk), None];
+    assert_eq!(
+        try_solve(&deck, &visible, &confirmed, &observed, 1, 1, 1, 0),
+        Err(SolveError::UnshowableConfirmed {
+            seat: 1,
+            confirmed: Drunk,
+            visible: Drunk,
+        })
+    );
+}
+
+#[test]
+fn test_corrupted_confessor_is_dizzy() {
+    use Role::*;
+    let deck = vec![Confessor, Judge, Poisoner];
+    let visible = vec![Some(Confessor), Some(Judge), None];
+    let confirmed = vec![Some(Confessor), Some(Judge), None];
+
+    for (judged_lying, expected) in [(false, 1), (true, 0)] {
+        let observed: Vec<RoleStatement> = vec![
+            ConfessorStatement::IAmDizzy.into(),
+            JudgeStatement {
+                target_index: 0,
+                is_lying: judged_lying,
+            }
+            .into(),
+            RoleStatement::NoStatement,
+        ];
+
+        // A good Confessor is only dizzy when corrupted, and saying so is the
+        // truth, so the Judge sees it as truthful
+        let solutions =
+            brute_force_solve_detailed(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+        assert_eq!(solutions.len(), expected, "Solutions: {:#?}", solutions);
+        for solution in &solutions {
+            assert_eq!(solution.true_roles, vec![Confessor, Judge, Poisoner]);
+            assert_eq!(solution.corrupted, vec![true, false, false]);
+        }
+    }
+
+    // Uncorrupted it can only say it's good
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmDizzy.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let constraints = Constraints {
+        known_corruptions: vec![Some(false), None, None],
+        ..Default::default()
+    };
+    assert!(brute_force_solve_constrained(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        &constraints,
+        2,
+        0,
+        1,
+        0
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_alchemist_cures_both_sides() {
+    use Role::*;
+    let deck = vec![Confessor, Alchemist, Hunter, Lover, Knight, Pooka];
+    let visible = vec![
+        None,
+        Some(Confessor),
+        Some(Alchemist),
+        Some(Lover),
+        Some(Hunter),
+    ];
+    // The Pooka corrupts seats 1 and 4, both within the Alchemist's reach
+    let confirmed = vec![
+        Some(Pooka),
+        Some(Confessor),
+        Some(Alchemist),
+        Some(Lover),
+        Some(Hunter),
+    ];
+    let observed = |corrupt_count| -> Vec<RoleStatement> {
+        vec![
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+            AlchemistStatement { corrupt_count }.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ]
+    };
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed(2), 4, 0, 0, 1);
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed(1), 4, 0, 0, 1);
+    assert!(
+        solutions.is_empty(),
+        "Unmatching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_corrupted_alchemist_cures_nothing() {
+    use Role::*;
+    let deck = vec![Confessor, Alchemist, Hunter, Lover, Knight, Pooka];
+    let visible = vec![
+        None,
+        Some(Alchemist),
+        Some(Hunter),
+        Some(Lover),
+        Some(Confessor),
+    ];
+    // The Pooka corrupts the Alchemist at seat 1 and the Confessor at seat 4
+    let confirmed = vec![
+        Some(Pooka),
+        Some(Alchemist),
+        Some(Hunter),
+        Some(Lover),
+        Some(Confessor),
+    ];
+    let observed = |corrupt_count| -> Vec<RoleStatement> {
+        vec![
+            RoleStatement::NoStatement,
+            AlchemistStatement { corrupt_count }.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+            ConfessorStatement::IAmDizzy.into(),
+        ]
+    };
+
+    // The Confessor stays dizzy, and the Alchemist lies about its cures
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed(1), 4, 0, 0, 1);
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed(0), 4, 0, 0, 1);
+    assert!(
+        solutions.is_empty(),
+        "Unmatching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_overlapping_alchemists_cure_independently() {
+    use Role::*;
+    let deck = vec![Confessor, Alchemist, Alchemist, Hunter, Knight, Pooka];
+    let visible = vec![
+        None,
+        Some(Confessor),
+        Some(Alchemist),
+        Some(Alchemist),
+        Some(Hunter),
+    ];
+    // Both corrupted seats (1 and 4) are in range of both Alchemists
+    let confirmed = vec![
+        Some(Pooka),
+        Some(Confessor),
+        Some(Alchemist),
+        Some(Alchemist),
+        Some(Hunter),
+    ];
+    let observed: Vec<RoleStatement> = vec![
+        RoleStatement::NoStatement,
+        ConfessorStatement::IAmGood.into(),
+        AlchemistStatement { corrupt_count: 2 }.into(),
+        AlchemistStatement { corrupt_count: 2 }.into(),
+        RoleStatement::NoStatement,
+    ];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 0, 1);
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_bombardier_counts_as_outcast() {
+    use Role::*;
+    let visible = vec![Some(Druid), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        DruidStatement {
+            target_indexes: to_bitvec(vec![1, 2]),
+            role: None,
+        }
+        .into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    // "NO Outcasts" among the other two seats can't hold with a Bombardier there
+    let deck = vec![Druid, Bombardier, Minion];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 1, 0);
+    for solution in &solutions {
+        assert!(
+            is_evil(&solution[0]),
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    let deck = vec![Druid, Knight, Minion];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert!(
+        solutions.iter().any(|solution| solution[0] == Druid),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_two_seat_natural_druid() {
+    use Role::*;
+    let visible = vec![Some(Druid), None, None];
+    let confirmed = vec![None; visible.len()];
+
+    let no_outcasts = Druid
+        .parse_natural_statement("Among #2, #3 there are NO Outcasts")
+        .unwrap();
+    assert_eq!(
+        no_outcasts,
+        DruidStatement {
+            target_indexes: to_bitvec(vec![1, 2]),
+            role: None,
+        }
+        .into()
+    );
+    let observed = vec![
+        no_outcasts,
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let deck = vec![Druid, Bombardier, Minion];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 1, 1, 1, 0);
+    assert!(
+        !solutions.is_empty() && solutions.iter().all(|solution| is_evil(&solution[0])),
+        "Unmatching solutions: {:#?}",
+        solutions
+    );
+
+    let hunter = Druid
+        .parse_natural_statement("Among #2, #3 there is: Hunter")
+        .unwrap();
+    assert_eq!(
+        hunter,
+        DruidStatement {
+            target_indexes: to_bitvec(vec![1, 2]),
+            role: Some(Hunter),
+        }
+        .into()
+    );
+    let observed = vec![
+        hunter,
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let deck = vec![Druid, Hunter, Knight, Minion];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert!(
+        solutions
+            .iter()
+            .any(|solution| solution[0] == Druid && solution[1..].contains(&Hunter)),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+    for solution in &solutions {
+        if solution[0] == Druid {
+            assert!(
+                solution[1..].contains(&Hunter),
+                "Unmatching solution found. Solutions: {:#?}",
+                solutions
+            );
+        }
+    }
+}
+
+#[test]
+fn test_witch_does_not_corrupt() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Witch];
+    let visible = vec![Some(Confessor), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmDizzy.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    // Nothing can make a real Confessor dizzy here, so it has to be the Witch
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    for solution in &solutions {
+        assert_eq!(
+            solution[0], Witch,
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_solutions_are_sorted() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Minion];
+    let visible = vec![None; 3];
+    let confirmed = vec![None; 3];
+    let observed = vec![RoleStatement::NoStatement; 3];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert_eq!(
+        solutions,
+        vec![
+            vec![Confessor, Lover, Minion],
+            vec![Confessor, Minion, Lover],
+            vec![Lover, Confessor, Minion],
+            vec![Lover, Minion, Confessor],
+            vec![Minion, Confessor, Lover],
+            vec![Minion, Lover, Confessor],
+        ]
+    );
+
+    let again = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert_eq!(solutions, again);
+}
+
+#[test]
+fn test_progress_reaches_total() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Lover, Knight, Judge, Minion];
+    let visible = vec![None; 5];
+    let confirmed = vec![None; 5];
+    let observed = vec![RoleStatement::NoStatement; 5];
+
+    let mut calls: Vec<(usize, usize)> = Vec::new();
+    let solutions = brute_force_solve_with_progress(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        4,
+        0,
+        1,
+        0,
+        |finished, total| calls.push((finished, total)),
+    );
+
+    // Five ways to pick four of the five villagers
+    assert!(!calls.is_empty() && calls.len() <= 5, "Calls: {:?}", calls);
+    assert_eq!(*calls.last().unwrap(), (5, 5));
+    assert!(
+        calls.windows(2).all(|w| w[0].0 < w[1].0),
+        "Calls: {:?}",
+        calls
+    );
+    assert_eq!(
+        solutions,
+        brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0)
+    );
+}
+
+#[test]
+fn test_visible_poet() {
+    use Role::*;
+    let visible = vec![Some(Confessor), Some(Poet), None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    let deck = vec![Confessor, Poet, Minion];
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    for solution in &solutions {
+        assert!(
+            solution[1] == Poet || is_evil(&solution[1]),
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        solutions.contains(&vec![Confessor, Poet, Minion]),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_known_demon() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Knight, Minion, Baa, Lilis];
+    let visible = vec![Some(Confessor), Some(Lover), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        LoverStatement { evil_count: 1 }.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let solve = |constraints: &Constraints| {
+        brute_force_solve_constrained(
+            &deck,
+            &visible,
+            &confirmed,
+            &observed,
+            constraints,
+            2,
+            0,
+            1,
+            1,
+        )
+    };
+
+    let solutions = solve(&Constraints::default());
+    assert!(solutions.iter().any(|solution| solution.contains(&Lilis)));
+
+    let known = solve(&Constraints {
+        known_demon: Some(Baa),
+        ..Default::default()
+    });
+    let expected: Vec<Vec<Role>> = solutions
+        .into_iter()
+        .filter(|solution| !solution.contains(&Lilis))
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(known, expected);
+}
+
+#[test]
+fn test_pooka_skips_outcast_neighbours() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Knight, Bombardier, Pooka];
+    let visible = vec![None, None, Some(Confessor), Some(Lover)];
+    let confirmed = vec![Some(Pooka), Some(Bombardier), None, None];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let solutions = brute_force_solve_detailed(&deck, &visible, &confirmed, &observed, 2, 1, 0, 1);
+    assert_eq!(solutions.len(), 1, "Solutions: {:#?}", solutions);
+    assert_eq!(
+        solutions[0].true_roles,
+        vec![Pooka, Bombardier, Confessor, Lover]
+    );
+    // Of its two neighbours only the Lover is a villager
+    assert_eq!(solutions[0].corrupted, vec![false, false, false, true]);
+}
+
+#[test]
+fn test_known_corruption() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Hunter, Knight, Judge, Poisoner, Minion];
+    let visible = vec![Some(Confessor), Some(Lover), Some(Hunter), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        LoverStatement { evil_count: 0 }.into(),
+        HunterStatement { distance: 2 }.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
+    assert!(
+        solutions.contains(&vec![Confessor, Lover, Hunter, Knight, Minion]),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    // A corrupted Hunter lies, so the Minion can no longer be two seats away
+    let constraints = Constraints {
+        known_corruptions: vec![None, None, Some(true), None, None],
+        ..Default::default()
+    };
+    let solutions = brute_force_solve_constrained(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        &constraints,
+        4,
+        0,
+        1,
+        0,
+    );
+    for solution in &solutions {
+        assert!(
+            solution.contains(&Poisoner),
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_slayer_kills_disguised_minion() {
+    use Role::*;
+    let deck = vec![Slayer, Knight, Confessor, Minion];
+    let visible = vec![Some(Slayer), Some(Knight), Some(Confessor)];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![
+        Slayer.parse_natural_statement("I killed Evil #2").unwrap(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    // The Knight is really the Minion, the Slayer goes by true alignment
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert_eq!(solutions, vec![vec![Slayer, Minion, Confessor]]);
+}
+
+#[test]
+fn test_slayer_cannot_kill_corrupted_good() {
+    use Role::*;
+    let deck = vec![Slayer, Knight, Poisoner];
+    let visible = vec![Some(Slayer), Some(Knight), None];
+    let confirmed = vec![None; visible.len()];
+    let constraints = Constraints {
+        known_corruptions: vec![None, Some(true), None],
+        ..Default::default()
+    };
+
+    for (alignment, expected) in [
+        // A Poisoner showing as the Slayer can't kill anyone either
+        (
+            Alignment::Good,
+            vec![
+                vec![Slayer, Knight, Poisoner],
+                vec![Poisoner, Knight, Slayer],
+            ],
+        ),
+        (Alignment::Evil, vec![]),
+    ] {
+        let observed = vec![
+            SlayerStatement {
+                target_index: 1,
+                alignment,
+            }
+            .into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ];
+        let solutions = brute_force_solve_constrained(
+            &deck,
+            &visible,
+            &confirmed,
+            &observed,
+            &constraints,
+            2,
+            0,
+            1,
+            0,
+        );
+        assert_eq!(solutions, expected, "{:?}", alignment);
+    }
+}
+
+#[test]
+fn test_force_lying() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Minion];
+    let visible = vec![Some(Confessor), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![
+        ConfessorStatement::IAmDizzy.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let solve = |constraints: &Constraints| {
+        brute_force_solve_constrained(
+            &deck,
+            &visible,
+            &confirmed,
+            &observed,
+            constraints,
+            2,
+            0,
+            1,
+            0,
+        )
+    };
+
+    // Nothing here corrupts, so only the Minion would claim to be dizzy
+    let solutions = solve(&Constraints::default());
+    assert!(!solutions.is_empty());
+    assert!(solutions.iter().all(|solution| solution[0] == Minion));
+
+    // Once the seat is known to lie the real Confessor fits too
+    let forced = solve(&Constraints {
+        force_lying: vec![true],
+        ..Default::default()
+    });
+    assert!(forced.iter().any(|solution| solution[0] == Confessor));
+    assert!(solutions.iter().all(|solution| forced.contains(solution)));
+}
+
+#[test]
+fn test_uncorrupted_bard_is_truthful() {
+    use Role::*;
+    let deck = vec![Bard, Confessor, Knight, Lover, Poisoner];
+    let visible = vec![Some(Bard), None, None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![
+        BardStatement { distance: Some(1) }.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let solve = |constraints: &Constraints| {
+        brute_force_solve_constrained(
+            &deck,
+            &visible,
+            &confirmed,
+            &observed,
+            constraints,
+            3,
+            0,
+            1,
+            0,
+        )
+    };
+
+    // A Poisoner next to the Bard can corrupt it into lying
+    let solutions = solve(&Constraints::default());
+    assert!(solutions.iter().any(|solution| solution[1] == Poisoner));
+    assert!(solutions.iter().any(|solution| solution[2] == Poisoner));
+
+    // Uncorrupted its claim has to hold, which only a Poisoner across from it
+    // allows
+    let pinned = solve(&Constraints {
+        uncorrupted: vec![true],
+        ..Default::default()
+    });
+    assert!(!pinned.is_empty());
+    assert!(pinned.iter().all(|solution| solution[2] == Poisoner));
+}
+
+#[test]
+fn test_diagnose_infeasible() {
+    use Role::*;
+    let puzzle = Puzzle {
+        deck: vec![Confessor, Lover, Hunter, Knight, Minion],
+        visible: vec![Some(Confessor), Some(Lover), Some(Hunter), Some(Knight)],
+        confirmed: vec![None; 4],
+        observed: vec![
+            ConfessorStatement::IAmGood.into(),
+            LoverStatement { evil_count: 0 }.into(),
+            HunterStatement { distance: 2 }.into(),
+            RoleStatement::NoStatement,
+        ],
+        villagers: 3,
+        outcasts: 0,
+        minions: 1,
+        demons: 0,
+    };
+
+    let solutions = brute_force_solve(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        puzzle.villagers,
+        puzzle.outcasts,
+        puzzle.minions,
+        puzzle.demons,
+    );
+    assert!(
+        solutions.is_empty(),
+        "Expected no solutions: {:#?}",
+        solutions
+    );
+
+    // The Lover is wrong both when the Minion poses as it and when it sits next to it
+    let ranked = diagnose_infeasible(&puzzle);
+    assert_eq!(
+        ranked.first().map(|&(seat, _)| seat),
+        Some(1),
+        "{:?}",
+        ranked
+    );
+    assert!(ranked.windows(2).all(|w| w[0].1 >= w[1].1), "{:?}", ranked);
+}
+
+#[test]
+fn test_confirmed_alignment() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Knight, Minion];
+    let visible = vec![None; 3];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert!(
+        solutions.iter().any(|solution| !is_evil(&solution[0])),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    let constraints = Constraints {
+        confirmed_alignment: vec![Some(Alignment::Evil), None, None],
+        ..Default::default()
+    };
+    let solutions = brute_force_solve_constrained(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        &constraints,
+        2,
+        0,
+        1,
+        0,
+    );
+    for solution in &solutions {
+        assert!(
+            is_evil(&solution[0]),
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_confirmed_group() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Knight, Bombardier, Minion, Baa];
+    let visible = vec![None; 4];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let constraints = Constraints {
+        confirmed_group: vec![None, Some(Group::Demon), None, None],
+        ..Default::default()
+    };
+    let solutions = brute_force_solve_constrained(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        &constraints,
+        1,
+        1,
+        1,
+        1,
+    );
+    for solution in &solutions {
+        assert!(
+            solution[1].group() == Group::Demon,
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    // Composes with the alignment confirmation
+    let constraints = Constraints {
+        confirmed_alignment: vec![Some(Alignment::Evil), None, None, None],
+        confirmed_group: vec![None, Some(Group::Demon), None, None],
+        ..Default::default()
+    };
+    let solutions = brute_force_solve_constrained(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        &constraints,
+        1,
+        1,
+        1,
+        1,
+    );
+    for solution in &solutions {
+        assert!(
+            solution[0] == Minion && solution[1] == Baa,
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_solve_iter_first_solution() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Lover, Knight, Minion];
+    let visible = vec![None; 5];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let all = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
+    let first: Vec<Vec<Role>> = solve_iter(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0)
+        .take(1)
+        .collect();
+    assert_eq!(first.len(), 1);
+    assert!(
+        all.contains(&first[0]),
+        "Unmatching solution found. Solutions: {:#?}",
+        first
+    );
+
+    let mut streamed: Vec<Vec<Role>> =
+        solve_iter(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0).collect();
+    streamed.sort();
+    assert_eq!(streamed, all);
+
+    // A seat that can't show its confirmed role leaves nothing to stream
+    let mut confirmed = confirmed;
+    let mut visible = visible;
+    visible[0] = Some(Confessor);
+    confirmed[0] = Some(Hunter);
+    assert_eq!(
+        solve_iter(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0).count(),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "The counts add up to 4 but there are 5 seats")]
+fn test_solve_iter_checks_counts() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Lover, Knight, Minion];
+    let visible = vec![None; 5];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+    let _ = solve_iter(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0);
+}
+
+#[test]
+fn test_no_demons() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Hunter, Knight, Minion];
+    let visible = vec![Some(Confessor), Some(Lover), Some(Hunter), None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        LoverStatement { evil_count: 0 }.into(),
+        HunterStatement { distance: 1 }.into(),
+        RoleStatement::NoStatement,
+    ];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0);
+    assert_eq!(
+        solutions,
+        vec![vec![Confessor, Lover, Hunter, Minion]],
+        "Unmatching solution found. Solutions: {:#?}",
+        solutions
+    );
+
+    // Nobody evil at all, a truthful Hunter has nothing to point at
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 0, 0);
+    assert!(
+        solutions.is_empty(),
+        "Unmatching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_two_demons() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Hunter, Knight, Bard, Baa, Lilis];
+    let visible = vec![Some(Confessor), None, Some(Lover), None, Some(Hunter)];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        LoverStatement { evil_count: 2 }.into(),
+        RoleStatement::NoStatement,
+        HunterStatement { distance: 1 }.into(),
+    ];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 0, 2);
+    for solution in &solutions {
+        assert!(
+            solution
+                .iter()
+                .filter(|r| r.group() == Group::Demon)
+                .count()
+                == 2,
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    assert!(
+        solutions.contains(&vec![Confessor, Baa, Lover, Lilis, Hunter]),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_solve_iter_shuffled() {
+    use Role::*;
+    // A single villager combination keeps the search on one thread, so the
+    // first solution only depends on the shuffled minion order
+    let deck = vec![Confessor, Knight, Minion, Poisoner, Witch];
+    let visible = vec![None; 3];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let first = |seed: u64| {
+        solve_iter_shuffled(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, seed)
+            .next()
+            .unwrap()
+    };
+    assert_eq!(first(0), vec![Confessor, Knight, Poisoner]);
+    assert_eq!(first(1), vec![Confessor, Knight, Witch]);
+    assert_eq!(first(1), first(1));
+
+    let all = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    let mut streamed: Vec<Vec<Role>> =
+        solve_iter_shuffled(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0, 1).collect();
+    streamed.sort();
+    assert_eq!(streamed, all);
+}
+
+#[test]
+fn test_solve_iter_shuffled_repeats_with_seed() {
+    use Role::*;
+    // Several villager combinations, which a parallel search would race through
+    let deck = vec![Confessor, Knight, Lover, Medium, Minion, Poisoner, Witch];
+    let visible = vec![None; 4];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let sequence = |seed: u64| -> Vec<Vec<Role>> {
+        solve_iter_shuffled(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0, seed).collect()
+    };
+    let first = sequence(7);
+    assert!(first.len() > 1);
+    for _ in 0..20 {
+        assert_eq!(sequence(7), first);
+    }
+}
+
+#[test]
+fn test_excluded_roles() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Knight, Minion];
+    let visible = vec![None; 3];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let constraints = Constraints {
+        excluded_roles: vec![vec![], vec![Minion], vec![]],
+        ..Default::default()
+    };
+    let solutions = brute_force_solve_constrained(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        &constraints,
+        2,
+        0,
+        1,
+        0,
+    );
+    for solution in &solutions {
+        assert!(
+            solution[1] != Minion,
+            "Unmatching solution found. Solutions: {:#?}",
+            solutions
+        );
+    }
+
+    let unconstrained = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert_eq!(
+        solutions.len(),
+        unconstrained.iter().filter(|s| s[1] != Minion).count()
+    );
+    assert!(
+        !solutions.is_empty(),
+        "No matching solution found. Solutions: {:#?}",
+        solutions
+    );
+}
+
+#[test]
+fn test_solver_context_reuse() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Hunter, Knight, Minion];
+    let context = SolverContext::new(&deck, 3, 0, 1, 0);
+    let visible = vec![Some(Confessor), Some(Lover), Some(Hunter), None];
+    let confirmed = vec![None; visible.len()];
+
+    for observed in [
+        vec![
+            ConfessorStatement::IAmGood.into(),
+            LoverStatement { evil_count: 1 }.into(),
+            HunterStatement { distance: 1 }.into(),
+            RoleStatement::NoStatement,
+        ],
+        vec![
+            ConfessorStatement::IAmGood.into(),
+            LoverStatement { evil_count: 0 }.into(),
+            HunterStatement { distance: 1 }.into(),
+            RoleStatement::NoStatement,
+        ],
+    ] {
+        let mut reused = context.solve_with(&visible, &confirmed, &observed);
+        let mut fresh = brute_force_solve(&deck, &visible, &confirmed, &observed, 3, 0, 1, 0);
+        reused.sort();
+        fresh.sort();
+        assert!(!fresh.is_empty(), "No matching solution found");
+        assert_eq!(reused, fresh);
+    }
+
+    // The context checks each board against its counts
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+    assert_eq!(
+        context.try_solve_with(&visible[..3], &confirmed[..3], &observed[..3]),
+        Err(SolveError::CountSumMismatch {
+            counts: 4,
+            seats: 3,
+        })
+    );
+    let mut confirmed = confirmed;
+    confirmed[0] = Some(Hunter);
+    assert_eq!(
+        context.try_solve_with(&visible, &confirmed, &observed),
+        Err(SolveError::UnshowableConfirmed {
+            seat: 0,
+            confirmed: Hunter,
+            visible: Confessor,
+        })
+    );
+    assert!(context
+        .solve_with(&visible, &confirmed, &observed)
+        .is_empty());
+    assert!(brute_force_solve_with_progress(
+        &deck,
+        &visible,
+        &confirmed,
+        &observed,
+        3,
+        0,
+        1,
+        0,
+        |_, _| {}
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_disguise_at() {
+    use Role::*;
+    let deck = vec![Confessor, Confessor, Minion];
+    let visible = vec![Some(Confessor), Some(Confessor), Some(Confessor)];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        ConfessorStatement::IAmGood.into(),
+        ConfessorStatement::IAmDizzy.into(),
+    ];
+
+    let solutions = brute_force_solve_detailed(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert_eq!(solutions.len(), 1, "Solutions: {:#?}", solutions);
+    let solution = &solutions[0];
+    assert_eq!(solution.true_roles, vec![Confessor, Confessor, Minion]);
+    assert_eq!(solution.disguised_roles, vec![Confessor; 3]);
+    assert_eq!(solution.disguise_at(0), None);
+    assert_eq!(solution.disguise_at(1), None);
+    assert_eq!(solution.disguise_at(2), Some(Confessor));
+    assert_eq!(solution.disguise_at(3), None);
+}
+
+#[test]
+fn test_solve_with_stats() {
+    use Role::*;
+    let puzzle = Puzzle {
+        deck: vec![Confessor, Knight, Lover, Poisoner],
+        visible: vec![Some(Confessor), Some(Knight), Some(Lover)],
+        confirmed: vec![None, Some(Knight), None],
+        observed: vec![
+            ConfessorStatement::IAmDizzy.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ],
+        villagers: 2,
+        outcasts: 0,
+        minions: 1,
+        demons: 0,
+    };
+
+    let (solutions, stats) = solve_with_stats(&puzzle).unwrap();
+    assert_eq!(solutions.len(), 2, "Solutions: {:#?}", solutions);
+    assert_eq!(stats.accepted, solutions.len());
+    assert!(stats.candidates >= stats.accepted, "{:?}", stats);
+    assert!(stats.pruned_by_confirmation > 0, "{:?}", stats);
+    assert!(stats.pruned_by_visible > 0, "{:?}", stats);
+}
+
+#[test]
+fn test_unconstrained_puzzle() {
+    use Role::*;
+    // Three minions can't all be disguised with a single Knight to show, so
+    // none of the seatings are even tried
+    let puzzle = Puzzle {
+        deck: vec![Knight, Minion, Poisoner, Witch],
+        visible: vec![None; 4],
+        confirmed: vec![None; 4],
+        observed: vec![RoleStatement::NoStatement; 4],
+        villagers: 1,
+        outcasts: 0,
+        minions: 3,
+        demons: 0,
+    };
+    let (solutions, stats) = solve_with_stats(&puzzle).unwrap();
+    assert!(solutions.is_empty(), "Solutions: {:#?}", solutions);
+    assert_eq!(stats.candidates, 0, "{:?}", stats);
+
+    // Otherwise every seating is a solution, with a disguise that fits it
+    let puzzle = Puzzle {
+        deck: vec![Confessor, Knight, Lover, Hunter, Minion, Baa],
+        villagers: 3,
+        minions: 1,
+        demons: 0,
+        ..puzzle
+    };
+    let (solutions, stats) = solve_with_stats(&puzzle).unwrap();
+    assert_eq!(stats.accepted, stats.candidates, "{:?}", stats);
+    assert_eq!(
+        brute_force_solve(
+            &puzzle.deck,
+            &puzzle.visible,
+            &puzzle.confirmed,
+            &puzzle.observed,
+            3,
+            0,
+            1,
+            0
+        ),
+        solutions
+            .iter()
+            .map(|solution| solution.true_roles.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(solutions.len(), 4 * 24);
+    for solution in &solutions {
+        let minion = solution
+            .true_roles
+            .iter()
+            .position(|r| *r == Minion)
+            .unwrap();
+        let shown = solution.disguised_roles[minion];
+        assert!(
+            puzzle.deck.contains(&shown) && shown.alignment() == Alignment::Good,
+            "{:?}",
+            solution
+        );
+    }
+}
+
+#[test]
+fn test_minimal_statement_set() {
+    use Role::*;
+    let deck = vec![Confessor, Knight, Lover, Hunter, Enlightened, Minion];
+    let board = vec![Lover, Hunter, Minion, Confessor, Knight];
+
+    let statements = minimal_statement_set(&board, &deck, 4, 0, 1, 0);
+    assert!(!statements.is_empty());
+
+    let mut visible = vec![None; board.len()];
+    let mut observed = vec![RoleStatement::NoStatement; board.len()];
+    for (seat, statement) in &statements {
+        assert_ne!(board[*seat], Minion, "{:?}", statements);
+        visible[*seat] = Some(board[*seat]);
+        observed[*seat] = statement.clone();
+    }
+    let confirmed = vec![None; board.len()];
+    assert_eq!(
+        brute_force_solve(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0),
+        vec![board]
+    );
+}
+
+#[test]
+fn test_consistent_statements_for_seat() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Lover, Knight, Minion];
+    let visible = vec![Some(Confessor), Some(Hunter), None, None, None];
+    let confirmed = vec![None, Some(Hunter), None, None, Some(Lover)];
+    let observed = vec![
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    // The Minion sits on seat 2 or 3, one or two seats from the Hunter
+    let solutions = brute_force_solve_detailed(&deck, &visible, &confirmed, &observed, 4, 0, 1, 0);
+    assert_eq!(solutions.len(), 2, "Solutions: {:#?}", solutions);
+
+    let statements = consistent_statements_for_seat(&solutions, 1, Hunter);
+    assert_eq!(statements.len(), 2, "{:?}", statements);
+    assert!(statements.contains(&HunterStatement { distance: 1 }.into()));
+    assert!(statements.contains(&HunterStatement { distance: 2 }.into()));
+    assert!(consistent_statements_for_seat(&[], 1, Hunter).is_empty());
+    assert_eq!(
+        consistent_statements_for_seat_on(&solutions, 1, Hunter, Topology::Circle),
+        statements
+    );
+}
+
+#[test]
+fn test_validate_candidate_on_line() {
+    use Role::*;
+    // The Minion is next to the Hunter around a circle, five seats away in a line
+    let candidate = vec![Hunter, Confessor, Knight, Lover, Poet, Minion];
+    let deck = candidate.clone();
+    let mut visible = vec![None; candidate.len()];
+    visible[0] = Some(Hunter);
+    let confirmed = vec![None; candidate.len()];
+    let mut observed = vec![RoleStatement::NoStatement; candidate.len()];
+    observed[0] = HunterStatement { distance: 5 }.into();
+
+    let validate = |topology| {
+        validate_candidate_on(
+            &candidate, &deck, &visible, &confirmed, &observed, 5, 0, 1, 0, topology,
+        )
+    };
+    assert_eq!(validate(Topology::Line), Ok(()));
+    assert!(validate(Topology::Circle).is_err());
+    assert!(
+        validate_candidate(&candidate, &deck, &visible, &confirmed, &observed, 5, 0, 1, 0).is_err()
+    );
+}
+
+#[test]
+fn test_group_by_demon() {
+    use Role::*;
+    let solutions = vec![
+        vec![Confessor, Minion, Lover, Baa, Knight],
+        vec![Confessor, Lover, Minion, Baa, Knight],
+        vec![Minion, Confessor, Lover, Baa, Knight],
+        vec![Confessor, Minion, Lover, Knight, Lilis],
+    ];
+    assert_eq!(group_by_demon(&solutions), vec![(3, Baa, 3), (4, Lilis, 1)]);
+    assert!(group_by_demon(&[vec![Confessor, Minion]]).is_empty());
+}
+
+#[test]
+fn test_solve_batch() {
+    use Role::*;
+    let first = Puzzle {
+        deck: vec![Confessor, Lover, Minion],
+        visible: vec![Some(Confessor), None, None],
+        confirmed: vec![None; 3],
+        observed: vec![
+            ConfessorStatement::IAmGood.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ],
+        villagers: 2,
+        outcasts: 0,
+        minions: 1,
+        demons: 0,
+    };
+    let second = Puzzle {
+        deck: vec![Confessor, Lover, Knight, Hunter, Minion, Baa],
+        visible: vec![Some(Confessor), Some(Lover), None, None, None],
+        confirmed: vec![None; 5],
+        observed: vec![
+            ConfessorStatement::IAmGood.into(),
+            LoverStatement { evil_count: 1 }.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ],
+        villagers: 3,
+        outcasts: 0,
+        minions: 1,
+        demons: 1,
+    };
+    let third = Puzzle {
+        deck: vec![Confessor, Knight, Lover, Poisoner],
+        visible: vec![Some(Confessor), Some(Knight), Some(Lover)],
+        confirmed: vec![None, Some(Knight), None],
+        observed: vec![
+            ConfessorStatement::IAmDizzy.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ],
+        villagers: 2,
+        outcasts: 0,
+        minions: 1,
+        demons: 0,
+    };
+    let puzzles = vec![first, second, third];
+
+    let expected: Vec<Vec<Vec<Role>>> = puzzles
+        .iter()
+        .map(|puzzle| {
+            brute_force_solve(
+                &puzzle.deck,
+                &puzzle.visible,
+                &puzzle.confirmed,
+                &puzzle.observed,
+                puzzle.villagers,
+                puzzle.outcasts,
+                puzzle.minions,
+                puzzle.demons,
+            )
+        })
+        .collect();
+    assert!(expected.iter().all(|solutions| !solutions.is_empty()));
+    assert_eq!(solve_batch(&puzzles), expected);
+    assert!(solve_batch(&[]).is_empty());
+}
+
+#[test]
+fn test_demon_seat_distribution() {
+    use Role::*;
+    let puzzle = Puzzle {
+        deck: vec![Confessor, Lover, Knight, Hunter, Minion, Baa],
+        visible: vec![Some(Confessor), Some(Lover), None, None, None],
+        confirmed: vec![None; 5],
+        observed: vec![
+            ConfessorStatement::IAmGood.into(),
+            LoverStatement { evil_count: 1 }.into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ],
+        villagers: 3,
+        outcasts: 0,
+        minions: 1,
+        demons: 1,
+    };
+
+    let solutions = brute_force_solve(
+        &puzzle.deck,
+        &puzzle.visible,
+        &puzzle.confirmed,
+        &puzzle.observed,
+        3,
+        0,
+        1,
+        1,
+    );
+    let mut expected: Vec<(usize, usize)> = Vec::new();
+    for (seat, _, count) in group_by_demon(&solutions) {
+        match expected.iter_mut().find(|(s, _)| *s == seat) {
+            Some((_, total)) => *total += count,
+            None => expected.push((seat, count)),
+        }
+    }
+    expected.sort();
+
+    let distribution = demon_seat_distribution(&puzzle);
+    assert!(distribution.len() > 1, "{:?}", distribution);
+    assert_eq!(distribution, expected);
+}
+
+#[test]
+fn test_distinct_up_to_rotation() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Minion];
+    let visible = vec![None; 3];
+    let confirmed = vec![None; visible.len()];
+    let observed = vec![RoleStatement::NoStatement; visible.len()];
+
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert_eq!(solutions.len(), 6);
+
+    // Clockwise and counter-clockwise orders can't be turned into each other
+    assert_eq!(
+        distinct_up_to_rotation(&solutions),
+        vec![
+            vec![Confessor, Lover, Minion],
+            vec![Confessor, Minion, Lover]
+        ]
+    );
+    assert!(distinct_up_to_rotation(&[]).is_empty());
+}
+
+#[test]
+fn test_definite_alignments() {
+    use Role::*;
+    let solutions = vec![
+        vec![Confessor, Minion, Lover, Baa],
+        vec![Confessor, Poisoner, Baa, Lover],
+        vec![Lover, Minion, Confessor, Baa],
+    ];
+    assert_eq!(
+        definite_alignments(&solutions),
+        vec![Some(Alignment::Good), Some(Alignment::Evil), None, None]
+    );
+    assert!(definite_alignments(&[]).is_empty());
+}
+
+#[test]
+fn test_summarize_positions() {
+    use Role::*;
+    let solutions = vec![
+        vec![Confessor, Minion, Lover, Baa],
+        vec![Confessor, Poisoner, Baa, Lover],
+        vec![Lover, Minion, Confessor, Baa],
+        vec![Confessor, Minion, Wretch, Lover],
+    ];
+    let summaries = summarize_positions(&solutions);
+    assert_eq!(summaries.len(), 4);
+
+    let seat = &summaries[2];
+    assert_eq!(
+        *seat,
+        PositionSummary {
+            index: 2,
+            possible: vec![Confessor, Lover, Wretch, Baa],
+        }
+    );
+    assert_eq!(
+        seat.possible_alignments(),
+        vec![Alignment::Good, Alignment::Evil]
+    );
+    assert_eq!(
+        seat.possible_groups(),
+        vec![Group::Villager, Group::Outcast, Group::Demon]
+    );
+
+    assert_eq!(summaries[1].possible_alignments(), vec![Alignment::Evil]);
+    assert!(summarize_positions(&[]).is_empty());
+}
+
+#[test]
+fn test_negated_dreamer() {
+    use Role::*;
+    let deck = vec![Dreamer, Confessor, Lover, Minion, Poisoner];
+    let visible = vec![Some(Dreamer), None, None];
+    let confirmed = vec![None; visible.len()];
+
+    let solve = |negated| {
+        let observed: Vec<RoleStatement> = vec![
+            DreamerStatement {
+                target_index: 1,
+                role: Minion,
+                negated,
+            }
+            .into(),
+            RoleStatement::NoStatement,
+            RoleStatement::NoStatement,
+        ];
+        brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0)
+    };
+
+    // "#2 could be Minion" leaves the Minion there, "could not be" rules it out
+    assert!(solve(false).iter().any(|board| board[1] == Minion));
+    let solutions = solve(true);
+    assert!(
+        solutions.iter().all(|board| board[1] != Minion),
+        "{:?}",
+        solutions
+    );
+    assert!(
+        solutions.iter().any(|board| board[1] == Poisoner),
+        "{:?}",
+        solutions
+    );
+
+    assert_eq!(
+        Dreamer.parse_natural_statement("#2 could not be: Minion"),
+        Ok(DreamerStatement {
+            target_index: 1,
+            role: Minion,
+            negated: true,
+        }
+        .into())
+    );
+    assert_eq!(
+        Dreamer.parse_statement("1;!minion"),
+        Dreamer.parse_natural_statement("#2 could not be: Minion")
+    );
+}
+
+#[test]
+fn test_unused_deck_roles() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Wretch, Minion];
+    let visible = vec![Some(Confessor), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    // No outcasts are in play, so the Wretch is never seated
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0);
+    assert!(!solutions.is_empty());
+    assert_eq!(unused_deck_roles(&deck, &solutions), vec![Wretch]);
+    assert_eq!(unused_deck_roles(&deck, &[]), deck);
+}
+
+#[test]
+fn test_try_solve_errors() {
+    use Role::*;
+    let deck = vec![Confessor, Lover, Minion];
+    let visible = vec![Some(Confessor), None, None];
+    let confirmed = vec![None; visible.len()];
+    let observed: Vec<RoleStatement> = vec![
+        ConfessorStatement::IAmGood.into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+
+    assert!(try_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0).is_ok());
+    assert_eq!(
+        try_solve(&deck, &visible, &confirmed, &observed[..2], 2, 0, 1, 0),
+        Err(SolveError::LengthMismatch {
+            visible: 3,
+            confirmed: 3,
+            observed: 2,
+        })
+    );
+    assert_eq!(
+        try_solve(&deck, &visible, &confirmed, &observed, 2, 0, 2, 0),
+        Err(SolveError::CountSumMismatch {
+            counts: 4,
+            seats: 3,
+        })
+    );
+    assert_eq!(
+        try_solve(&deck, &visible, &confirmed, &observed, 3, 0, 0, 0),
+        Err(SolveError::DeckUndersupplied {
+            group: Group::Villager,
+            needed: 3,
+            available: 2,
+        })
+    );
+
+    let mut out_of_range = observed.clone();
+    out_of_range[1] = GemcrafterStatement { target_index: 5 }.into();
+    let error = try_solve(&deck, &visible, &confirmed, &out_of_range, 2, 0, 1, 0).unwrap_err();
+    assert_eq!(error, SolveError::IndexOutOfRange { seat: 1, seats: 3 });
+    assert_eq!(
+        error.to_string(),
+        "Seat 2 refers to a seat past the 3 in play"
+    );
+
+    let deck = vec![Empress, Lover, Minion];
+    let visible = vec![Some(Empress), None, None];
+    let names_itself: Vec<RoleStatement> = vec![
+        EmpressStatement {
+            target_indexes: to_bitvec(vec![0, 1, 2]),
+        }
+        .into(),
+        RoleStatement::NoStatement,
+        RoleStatement::NoStatement,
+    ];
+    let error = try_solve(&deck, &visible, &confirmed, &names_itself, 2, 0, 1, 0).unwrap_err();
+    assert_eq!(error, SolveError::NamesSpeaker { seat: 0 });
+    assert_eq!(
+        error.to_string(),
+        "Seat 1 names its own seat, which its role never does"
+    );
+    // The solvers that don't return errors find nothing, whether or not they check
+    assert!(brute_force_solve(&deck, &visible, &confirmed, &names_itself, 2, 0, 1, 0).is_empty());
+    assert!(
+        brute_force_solve_detailed(&deck, &visible, &confirmed, &names_itself, 2, 0, 1, 0)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_confirmed_role_must_show_as_visible() {
+    use Role::*;
+    let deck = vec![Confessor, Hunter, Lover, Minion];
+    let observed = vec![RoleStatement::NoStatement; 3];
+
+    // A minion disguises as any good role in the deck
+    let visible = vec![Some(Confessor), Some(Lover), None];
+    let confirmed = vec![Some(Minion), None, None];
+    let solutions = try_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0).unwrap();
+    assert!(!solutions.is_empty());
+    assert!(solutions.iter().all(|board| board[0] == Minion));
+
+    // A Hunter always shows itself
+    let confirmed = vec![Some(Hunter), None, None];
+    let error = try_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0).unwrap_err();
+    assert_eq!(
+        error,
+        SolveError::UnshowableConfirmed {
+            seat: 0,
+            confirmed: Hunter,
+            visible: Confessor,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Seat 1 is confirmed as Hunter but shows Confessor, which it can't disguise as"
+    );
+    assert!(brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 1, 0).is_empty());
+    let puzzle = Puzzle {
+        deck,
+        visible,
+        confirmed,
+        observed,
+        villagers: 2,
+        outcasts: 0,
+        minions: 1,
+        demons: 0,
+    };
+    assert_eq!(solve_batch(&[puzzle]), vec![Vec::<Vec<Role>>::new()]);
+}
+
+#[test]
+fn test_scout_only_one_evil_with_two_in_play() {
+    use Role::*;
+    let deck = vec![Scout, Confessor, Lover, Minion, Witch];
+    let visible = vec![Some(Scout), None, None, None];
+    let confirmed = vec![None; visible.len()];
+    let mut observed = vec![RoleStatement::NoStatement; visible.len()];
+    observed[0] = ScoutStatement {
+        role: None,
+        distance: 0,
+    }
+    .into();
+
+    assert_eq!(forced_liars(&observed, 1, 0), Vec::<usize>::new());
+    assert_eq!(forced_liars(&observed, 2, 0), vec![0]);
+    assert_eq!(forced_liars(&observed, 1, 1), vec![0]);
+
+    // Nothing corrupts here, so the Scout can only be a minion in disguise
+    let solutions = brute_force_solve(&deck, &visible, &confirmed, &observed, 2, 0, 2, 0);
+    assert!(!solutions.is_empty());
+    assert!(
+        solutions.iter().all(|board| board[0].alignment().is_evil()),
+        "Solutions: {:#?}",
+        solutions
+    );
+}
@@ -0,0 +1,118 @@
+use demon_deduce::roles::*;
+use demon_deduce::Role;
+use strum::IntoEnumIterator;
+
+const BOARDS: usize = 2000;
+
+/// Small xorshift generator so every run walks the same boards
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// A statement of the same kind that the truthful seat could not have made
+fn mutate(statement: &RoleStatement, len: usize) -> Option<RoleStatement> {
+    let mutated = match statement {
+        RoleStatement::Architect(s) => match s {
+            ArchitectStatement::Left => ArchitectStatement::Right,
+            ArchitectStatement::Right => ArchitectStatement::Equal,
+            ArchitectStatement::Equal => ArchitectStatement::Left,
+        }
+        .into(),
+        RoleStatement::Bard(BardStatement { distance }) => BardStatement {
+            distance: match distance {
+                Some(d) => Some(d + 1),
+                None => Some(1),
+            },
+        }
+        .into(),
+        RoleStatement::Confessor(_) => ConfessorStatement::IAmDizzy.into(),
+        RoleStatement::Enlightened(s) => match s {
+            EnlightenedStatement::Clockwise => EnlightenedStatement::CounterClockwise,
+            EnlightenedStatement::CounterClockwise => EnlightenedStatement::Equidistant,
+            EnlightenedStatement::Equidistant => EnlightenedStatement::Clockwise,
+        }
+        .into(),
+        RoleStatement::Hunter(HunterStatement { distance }) => HunterStatement {
+            distance: (distance + 1) % (len + 1),
+        }
+        .into(),
+        RoleStatement::Judge(JudgeStatement {
+            target_index,
+            is_lying,
+        }) => JudgeStatement {
+            target_index: *target_index,
+            is_lying: !is_lying,
+        }
+        .into(),
+        RoleStatement::Knitter(KnitterStatement { adjacent_count }) => KnitterStatement {
+            adjacent_count: adjacent_count + 1,
+        }
+        .into(),
+        RoleStatement::Lover(LoverStatement { evil_count }) => LoverStatement {
+            evil_count: evil_count + 1,
+        }
+        .into(),
+        _ => return None,
+    };
+    Some(mutated)
+}
+
+#[test]
+fn test_possible_statements_round_trip() {
+    let roles: Vec<Role> = Role::iter().collect();
+    let mut rng = Rng(0x5eed_1815);
+
+    for _ in 0..BOARDS {
+        let len = 3 + rng.below(8);
+        let board: Vec<Role> = (0..len).map(|_| roles[rng.below(roles.len())]).collect();
+        let corruptions: Vec<bool> = (0..len).map(|_| rng.below(4) == 0).collect();
+        let uncorruptions = vec![0; len];
+
+        for (position, &role) in board.iter().enumerate() {
+            for statement in possible_statements(role, &board, &board, &corruptions, position) {
+                let check = |statement: &RoleStatement| {
+                    can_produce_statement(
+                        role,
+                        false,
+                        &board,
+                        &board,
+                        &corruptions,
+                        &uncorruptions,
+                        position,
+                        statement,
+                    )
+                };
+
+                assert!(
+                    check(&statement),
+                    "{:?} at {} rejected {} on {:?}",
+                    role,
+                    position,
+                    statement,
+                    board
+                );
+                if let Some(mutated) = mutate(&statement, len) {
+                    assert!(
+                        !check(&mutated),
+                        "{:?} at {} accepted {} on {:?}",
+                        role,
+                        position,
+                        mutated,
+                        board
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use demon_deduce::roles::*;
+use demon_deduce::Role;
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+/// Statements a seat can make whether or not it's lying, because of how the
+/// role's ability works
+fn overlap_expected(statement: &RoleStatement, board: &[Role], position: usize) -> bool {
+    match statement {
+        // Roles without a statement say nothing either way
+        RoleStatement::NoStatement => true,
+        // The Dreamer only learns the true role of evil seats
+        RoleStatement::Dreamer(DreamerStatement { target_index, .. }) => {
+            board[*target_index].alignment() == Alignment::Good
+        }
+        // A fake Slayer never kills anyone, so it always reports a good target
+        RoleStatement::Slayer(SlayerStatement {
+            target_index,
+            alignment: Alignment::Good,
+        }) => board[*target_index].alignment() == Alignment::Good,
+        // Evil seats are never corrupt, so a lying Plague Doctor can clear itself
+        RoleStatement::PlagueDoctor(PlagueDoctorStatement {
+            corruption_index,
+            evil_index: None,
+        }) => *corruption_index == position,
+        // A lying Oracle names no evil seat. When none of the named seats is evil
+        // and one of them is in a good group, a truthful Oracle could say the same.
+        RoleStatement::Oracle(OracleStatement {
+            target_indexes,
+            claim: OracleClaim::Group(group),
+        }) => {
+            !group.default_alignment().is_evil()
+                && target_indexes
+                    .iter_ones()
+                    .all(|i| !board[i].alignment().is_evil())
+        }
+        _ => false,
+    }
+}
+
+/// Every statement of `role`'s kind on a board of `len` seats, or nothing if
+/// the role doesn't make statements `can_produce_statement` knows about
+fn all_statements(role: Role, len: usize) -> Vec<RoleStatement> {
+    let seats = 0..len;
+    let pairs = || {
+        seats
+            .clone()
+            .combinations(2)
+            .chain(seats.clone().combinations(3))
+            .map(to_bitvec)
+    };
+    let distances = 1..=max_ring_distance(len);
+
+    match role {
+        Role::Alchemist => (0..=3)
+            .map(|corrupt_count| AlchemistStatement { corrupt_count }.into())
+            .collect(),
+        Role::Architect => vec![
+            ArchitectStatement::Left.into(),
+            ArchitectStatement::Right.into(),
+            ArchitectStatement::Equal.into(),
+        ],
+        Role::Bard => std::iter::once(None)
+            .chain(distances.map(Some))
+            .map(|distance| BardStatement { distance }.into())
+            .collect(),
+        Role::Bishop => pairs()
+            .map(|target_indexes| BishopStatement { target_indexes }.into())
+            .collect(),
+        Role::Confessor => vec![
+            ConfessorStatement::IAmGood.into(),
+            ConfessorStatement::IAmDizzy.into(),
+        ],
+        Role::Dreamer => seats
+            .cartesian_product(Role::iter())
+            .cartesian_product([false, true])
+            .map(|((target_index, role), negated)| {
+                DreamerStatement {
+                    target_index,
+                    role,
+                    negated,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Druid => pairs()
+            .cartesian_product(std::iter::once(None).chain(Role::iter().map(Some)))
+            .map(|(target_indexes, role)| {
+                DruidStatement {
+                    target_indexes,
+                    role,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Empress => pairs()
+            .map(|target_indexes| EmpressStatement { target_indexes }.into())
+            .collect(),
+        Role::Enlightened => vec![
+            EnlightenedStatement::Clockwise.into(),
+            EnlightenedStatement::CounterClockwise.into(),
+            EnlightenedStatement::Equidistant.into(),
+        ],
+        Role::FortuneTeller => pairs()
+            .cartesian_product([false, true])
+            .map(|(target_indexes, is_evil)| {
+                FortuneTellerStatement {
+                    target_indexes,
+                    is_evil,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Gemcrafter => seats
+            .map(|target_index| GemcrafterStatement { target_index }.into())
+            .collect(),
+        Role::Hunter => distances
+            .map(|distance| HunterStatement { distance }.into())
+            .collect(),
+        Role::Jester => pairs()
+            .cartesian_product(0..=3)
+            .map(|(target_indexes, evil_count)| {
+                JesterStatement {
+                    target_indexes,
+                    evil_count,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Judge => seats
+            .cartesian_product([false, true])
+            .map(|(target_index, is_lying)| {
+                JudgeStatement {
+                    target_index,
+                    is_lying,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Knitter => (0..=len)
+            .map(|adjacent_count| KnitterStatement { adjacent_count }.into())
+            .collect(),
+        Role::Lover => (0..=3)
+            .map(|evil_count| LoverStatement { evil_count }.into())
+            .collect(),
+        Role::Medium => seats
+            .cartesian_product(Role::iter())
+            .map(|(target_index, role)| MediumStatement { target_index, role }.into())
+            .collect(),
+        Role::Oracle => pairs()
+            .cartesian_product(
+                Role::iter()
+                    .filter(|role| role.alignment() == Alignment::Evil)
+                    .map(OracleClaim::Role)
+                    .chain(
+                        [Group::Villager, Group::Outcast, Group::Minion, Group::Demon]
+                            .map(OracleClaim::Group),
+                    ),
+            )
+            .map(|(target_indexes, claim)| {
+                OracleStatement {
+                    target_indexes,
+                    claim,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Scout => std::iter::once(None)
+            .chain(Role::iter().map(Some))
+            .cartesian_product(distances)
+            .map(|(role, distance)| ScoutStatement { role, distance }.into())
+            .collect(),
+        Role::Slayer => seats
+            .cartesian_product([Alignment::Good, Alignment::Evil])
+            .map(|(target_index, alignment)| {
+                SlayerStatement {
+                    target_index,
+                    alignment,
+                }
+                .into()
+            })
+            .collect(),
+        Role::PlagueDoctor => seats
+            .clone()
+            .cartesian_product(std::iter::once(None).chain(seats.map(Some)))
+            .map(|(corruption_index, evil_index)| {
+                PlagueDoctorStatement {
+                    corruption_index,
+                    evil_index,
+                }
+                .into()
+            })
+            .collect(),
+        Role::Bombardier | Role::Knight | Role::Poet | Role::Wretch => {
+            vec![RoleStatement::NoStatement]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn test_truthful_and_lying_never_agree() {
+    use Role::*;
+    let boards = [
+        vec![Lover, Minion, Knight, Hunter, Baa, Confessor],
+        vec![Scout, Knight, Poisoner, Bombardier, Lover, Judge, Baa],
+        vec![
+            Knight, Witch, Minion, Confessor, Wretch, Hunter, Lover, Pooka,
+        ],
+        vec![Druid, Confessor, Minion],
+    ];
+
+    let mut overlapping = Vec::new();
+    for board in &boards {
+        let len = board.len();
+        let corruptions: Vec<bool> = (0..len).map(|i| i % 3 == 1).collect();
+        let uncorruptions: Vec<usize> = (0..len).map(|i| i % 2).collect();
+
+        for role in Role::iter() {
+            for position in 0..len {
+                for statement in all_statements(role, len) {
+                    let check = |is_lying| {
+                        can_produce_statement(
+                            role,
+                            is_lying,
+                            board,
+                            board,
+                            &corruptions,
+                            &uncorruptions,
+                            position,
+                            &statement,
+                        )
+                    };
+                    if check(false) && check(true) && !overlap_expected(&statement, board, position)
+                    {
+                        overlapping.push(format!(
+                            "{:?} at {} can say '{}' either way on {:?}",
+                            role, position, statement, board
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(overlapping.is_empty(), "{:#?}", overlapping);
+}